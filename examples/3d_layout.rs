@@ -26,7 +26,8 @@ use bevy::{
 };
 use bevy_rectray::{
     layout::{Container, LayoutObject, StackLayout},
-    Anchor, Dimension, RectrayFrame, RectrayPlugin, Transform2D,
+    Anchor, Dimension, RectrayFrame, RectrayMeshPlugin, RectrayPlugin, SyncMeshDimension,
+    Transform2D,
 };
 
 pub fn main() {
@@ -41,6 +42,7 @@ pub fn main() {
         .add_plugins(FrameTimeDiagnosticsPlugin)
         .add_systems(Startup, init)
         .add_plugins(RectrayPlugin)
+        .add_plugins(RectrayMeshPlugin)
         .insert_resource(AmbientLight {
             color: Color::WHITE,
             brightness: 1000.,
@@ -92,15 +94,21 @@ pub fn init(
                 .with_children(|builder| {
                     for i in HashSet::<u32>::from_iter(0u32..10u32) {
                         let pos = Vec2::new(fastrand::f32() * 1.5 + 0.5, 1.);
-                        builder.spawn((
-                            Mesh3d(meshes.add(random_mesh(i).scaled_by(pos.extend(1.)))),
-                            MeshMaterial3d(mat.clone()),
-                            Transform2D {
-                                anchor: Anchor::CENTER_LEFT,
-                                ..Default::default()
-                            },
-                            Dimension(Vec2::new(2.0, 2.0) * pos),
-                        ));
+                        builder
+                            .spawn((
+                                Transform2D {
+                                    anchor: Anchor::CENTER_LEFT,
+                                    ..Default::default()
+                                },
+                                Dimension(Vec2::new(2.0, 2.0) * pos),
+                            ))
+                            .with_child((
+                                Mesh3d(meshes.add(random_mesh(i))),
+                                MeshMaterial3d(mat.clone()),
+                                SyncMeshDimension {
+                                    size: Vec2::new(2.0, 2.0),
+                                },
+                            ));
                     }
                 });
         });
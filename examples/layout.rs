@@ -7,7 +7,7 @@ use bevy::{
     render::render_resource::{Extent3d, TextureDimension, TextureFormat},
 };
 use bevy_rectray::{
-    layout::{Container, LayoutObject, SpanLayout, StackLayout},
+    layout::{Container, SpanLayout, StackLayout},
     Anchor, Dimension, RectrayFrame, RectrayPlugin, Transform2D,
 };
 
@@ -51,11 +51,7 @@ pub fn init(mut commands: Commands) {
                 .spawn((
                     Transform2D::UNIT.with_offset(Vec2::new(0., -20.)),
                     Dimension(Vec2::new(250., 25.)),
-                    Container {
-                        layout: LayoutObject::new(StackLayout::HSTACK),
-                        margin: Vec2::new(1.0, 1.0),
-                        ..Default::default()
-                    },
+                    Container::new(StackLayout::HSTACK).with_margin(Vec2::new(1.0, 1.0)),
                     Visibility::Inherited,
                 ))
                 .with_children(|builder| {
@@ -81,11 +77,7 @@ pub fn init(mut commands: Commands) {
                     Sprite::default(),
                     Transform2D::UNIT.with_offset(Vec2::new(0., 20.)),
                     Dimension(Vec2::new(250., 25.)),
-                    Container {
-                        layout: LayoutObject::new(SpanLayout::HBOX),
-                        margin: Vec2::new(1.0, 1.0),
-                        ..Default::default()
-                    },
+                    Container::new(SpanLayout::HBOX).with_margin(Vec2::new(1.0, 1.0)),
                 ))
                 .with_children(|builder| {
                     for i in HashSet::<usize>::from_iter(0usize..9usize) {
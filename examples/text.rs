@@ -1,18 +1,19 @@
 use std::iter::repeat;
 
+use bevy::app::PluginGroup;
 use bevy::app::{App, Startup};
-use bevy::app::{PluginGroup, Update};
-use bevy::ecs::system::{Commands, Query};
+use bevy::ecs::system::Commands;
 use bevy::hierarchy::BuildChildren;
 use bevy::math::Vec2;
 use bevy::{
     color::palettes::css::GOLD,
     diagnostic::FrameTimeDiagnosticsPlugin,
     prelude::{Camera2d, ChildBuild, Visibility},
-    text::{Text2d, TextColor, TextFont, TextLayoutInfo},
+    text::{Text2d, TextColor, TextFont},
     window::{Window, WindowPlugin},
     DefaultPlugins,
 };
+use bevy_rectray::text::{RectrayTextPlugin, SyncTextDimension};
 use bevy_rectray::{
     layout::{Container, LayoutControl, LayoutObject, ParagraphLayout},
     Anchor, Dimension, RectrayFrame, RectrayPlugin, Transform2D,
@@ -32,8 +33,8 @@ pub fn main() {
         }))
         .add_plugins(FrameTimeDiagnosticsPlugin)
         .add_systems(Startup, init)
-        .add_systems(Update, sync_size)
         .add_plugins(RectrayPlugin)
+        .add_plugins(RectrayTextPlugin)
         .run();
 }
 
@@ -69,6 +70,7 @@ pub fn init(mut commands: Commands) {
                                 anchor: Anchor::TOP_LEFT,
                                 ..Default::default()
                             },
+                            SyncTextDimension,
                             if word == " " {
                                 LayoutControl::WhiteSpace
                             } else {
@@ -79,9 +81,3 @@ pub fn init(mut commands: Commands) {
                 });
         });
 }
-
-pub fn sync_size(mut query: Query<(&TextLayoutInfo, &mut Dimension)>) {
-    for (info, mut dimension) in query.iter_mut() {
-        dimension.0 = info.size;
-    }
-}
@@ -12,11 +12,12 @@ use bevy::{
     color::palettes::{basic::AQUA, css::GOLD},
     diagnostic::FrameTimeDiagnosticsPlugin,
     prelude::{Camera2d, ChildBuild, Visibility},
-    text::{Text2d, TextColor, TextFont, TextLayoutInfo},
+    text::{Text2d, TextColor, TextFont},
     window::{PrimaryWindow, SystemCursorIcon, Window, WindowPlugin},
     winit::cursor::CursorIcon,
     DefaultPlugins,
 };
+use bevy_rectray::text::{RectrayTextPlugin, SyncTextDimension};
 use bevy_rectray::{
     layout::{Container, LayoutControl, LayoutObject, ParagraphLayout},
     Anchor, Dimension, RectrayFrame, RectrayPickable, RectrayPlugin, Transform2D,
@@ -35,9 +36,9 @@ pub fn main() {
         }))
         .add_plugins(FrameTimeDiagnosticsPlugin)
         .add_systems(Startup, init)
-        .add_systems(Update, sync_size)
         .add_systems(Update, picking_cursor)
         .add_plugins(RectrayPlugin)
+        .add_plugins(RectrayTextPlugin)
         .run();
 }
 
@@ -76,6 +77,7 @@ pub fn init(mut commands: Commands) {
                                 scale: Vec2::splat(fastrand::f32() * 0.4 + 0.8),
                                 ..Default::default()
                             },
+                            SyncTextDimension,
                             if word == " " {
                                 LayoutControl::WhiteSpace
                             } else {
@@ -89,12 +91,6 @@ pub fn init(mut commands: Commands) {
         });
 }
 
-pub fn sync_size(mut query: Query<(&TextLayoutInfo, &mut Dimension)>) {
-    for (info, mut dimension) in query.iter_mut() {
-        dimension.0 = info.size;
-    }
-}
-
 pub fn picking_cursor(
     mut commands: Commands,
     window: Query<Entity, With<PrimaryWindow>>,
@@ -0,0 +1,99 @@
+//! Rotate widgets to face the camera.
+//!
+//! Add [`RectrayBillboardPlugin`] and add [`Billboard`] to a widget or a [`RectrayFrame`](crate::RectrayFrame).
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::{
+    component::Component, entity::Entity, reflect::ReflectComponent, schedule::IntoSystemConfigs,
+    system::Query,
+};
+use bevy::hierarchy::Parent;
+use bevy::math::{Quat, Vec2, Vec3};
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::render::camera::Camera;
+use bevy::transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+use crate::{RectrayTransformSet, RotatedRect, Transform2D};
+
+/// Rotate a widget so it faces the camera, its pivot from layout is not moved.
+///
+/// The widget is aligned to the camera's view plane, its own [`Transform2D::rotation`](crate::Transform2D::rotation)
+/// is applied on top of that. Rotation is around [`Transform2D::center`](crate::Transform2D::center),
+/// the same as its own rotation.
+///
+/// Uses the `GlobalTransform` of the parent and the camera from the previous frame.
+///
+/// # Picking
+///
+/// The picking backend tests against the plane of the widget's own `GlobalTransform`,
+/// so billboarded widgets are picked as rendered.
+/// [`RectrayPickingGrid`](crate::RectrayPickingGrid) bins rects projected onto the plane of their frame,
+/// so a billboarded widget viewed at an angle could be missed if the grid is used.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq)]
+#[reflect(Component, Default)]
+pub struct Billboard {
+    /// The camera to face, if not set, use the active camera with the highest `order`.
+    pub camera: Option<Entity>,
+}
+
+/// [`Plugin`] for [`Billboard`].
+#[derive(Debug, Clone, Copy)]
+pub struct RectrayBillboardPlugin;
+
+impl Plugin for RectrayBillboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Billboard>();
+        app.add_systems(
+            PostUpdate,
+            rectray_billboard
+                .after(RectrayTransformSet)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Rotate entities with [`Billboard`] to face their camera.
+pub fn rectray_billboard(
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    mut query: Query<(
+        &Billboard,
+        &mut Transform,
+        Option<&RotatedRect>,
+        Option<&Transform2D>,
+        Option<&Parent>,
+    )>,
+    parents: Query<&GlobalTransform>,
+) {
+    let active = cameras
+        .iter()
+        .filter(|(_, camera, _)| camera.is_active)
+        .max_by_key(|(_, camera, _)| camera.order)
+        .map(|(entity, ..)| entity);
+    for (billboard, mut transform, rect, transform_2d, parent) in query.iter_mut() {
+        let Some(Ok((.., camera))) = billboard.camera.or(active).map(|e| cameras.get(e)) else {
+            continue;
+        };
+        let parent_rotation = parent
+            .and_then(|parent| parents.get(parent.get()).ok())
+            .map_or(Quat::IDENTITY, |global| global.compute_transform().rotation);
+        let own_rotation = Quat::from_rotation_z(rect.map_or(0.0, |rect| rect.rotation));
+        let rotation =
+            parent_rotation.inverse() * camera.compute_transform().rotation * own_rotation;
+        // `Transform` is placed on the center of the rect, find the pivot in local space.
+        let pivot = match (rect, transform_2d) {
+            (Some(rect), Some(transform_2d)) => {
+                let pivot = rect.dimension * transform_2d.get_center() / rect.scale;
+                Vec2::select(pivot.is_finite_mask(), pivot, Vec2::ZERO).extend(0.0)
+            }
+            _ => Vec3::ZERO,
+        };
+        let translation = transform.transform_point(pivot) - rotation * (transform.scale * pivot);
+        if transform.rotation != rotation || !transform.translation.abs_diff_eq(translation, 1e-4) {
+            transform.rotation = rotation;
+            transform.translation = translation;
+        }
+    }
+}
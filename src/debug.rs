@@ -0,0 +1,85 @@
+//! Debug overlay for `bevy_rectray`, requires the `debug` feature.
+//!
+//! Add [`RectrayDebugPlugin`] to draw every computed [`RotatedRect`] with [`Gizmos`].
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::color::Color;
+use bevy::ecs::{
+    schedule::IntoSystemConfigs,
+    system::{Query, Res, Resource},
+};
+use bevy::gizmos::gizmos::Gizmos;
+use bevy::math::{Isometry3d, Vec2, Vec3Swizzles};
+use bevy::transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+use crate::{RotatedRect, Transform2D};
+
+/// Configuration of [`RectrayDebugPlugin`], can be modified at runtime.
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct RectrayDebugConfig {
+    /// If false, draw nothing.
+    pub enabled: bool,
+    /// Color of the outlines.
+    pub color: Color,
+    /// If true, draw an arrow from the center of each rect to its anchor.
+    pub draw_anchors: bool,
+}
+
+impl Default for RectrayDebugConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            color: Color::srgb(0.0, 1.0, 0.5),
+            draw_anchors: true,
+        }
+    }
+}
+
+/// [`Plugin`] that draws outlines of all [`RotatedRect`]s in world space.
+#[derive(Debug, Clone, Copy)]
+pub struct RectrayDebugPlugin;
+
+impl Plugin for RectrayDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RectrayDebugConfig>();
+        app.add_systems(
+            PostUpdate,
+            draw_rectray_debug.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Draw outlines, centers and anchors of [`RotatedRect`]s.
+pub fn draw_rectray_debug(
+    config: Res<RectrayDebugConfig>,
+    mut gizmos: Gizmos,
+    query: Query<(&RotatedRect, &Transform2D, &Transform, &GlobalTransform)>,
+) {
+    if !config.enabled {
+        return;
+    }
+    for (rect, transform_2d, transform, global) in query.iter() {
+        // `RotatedRect` is in the local space of the parent.
+        let parent = global.affine() * transform.compute_affine().inverse();
+        // `Transform` is placed on the center of the rect.
+        let rect = RotatedRect {
+            center: transform.translation.xy(),
+            ..*rect
+        };
+        let world = |p: Vec2| parent.transform_point3(p.extend(rect.z));
+        let [a, b, c, d] = rect.corners().map(world);
+        gizmos.linestrip([a, b, c, d, a], config.color);
+        let center = world(rect.center);
+        let size = (rect.dimension * rect.scale).min_element() * 0.05;
+        gizmos.cross(Isometry3d::from_translation(center), size, config.color);
+        if config.draw_anchors {
+            let anchor = world(rect.anchor(transform_2d.anchor));
+            if anchor != center {
+                gizmos.arrow(center, anchor, config.color);
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Drag to reorder children of a [`Container`].
+//!
+//! Add [`RectrayDragPlugin`] and add [`RectrayDraggable`] and [`RectrayPickable`](crate::RectrayPickable)
+//! to children of a container.
+//!
+//! Requires `bevy_picking`'s [`RayMap`] to find the pointer in the container.
+
+use bevy::app::{App, Plugin, Update};
+use bevy::ecs::{
+    component::Component,
+    event::EventReader,
+    query::With,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy::hierarchy::{Children, Parent};
+use bevy::math::Vec3Swizzles;
+use bevy::picking::{
+    backend::ray::RayMap,
+    events::{Drag, Pointer},
+};
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::transform::components::{GlobalTransform, Transform};
+
+use crate::layout::{Container, LayoutControl};
+use crate::picking::ray_cast_rect;
+use crate::RotatedRect;
+
+/// Allow an item to be reordered among its siblings in a [`Container`] by dragging.
+///
+/// When dragged, the item swaps places with the sibling closest to the pointer,
+/// this modifies the order of `Children`. Dragging outside of the container has no effect.
+///
+/// Siblings with [`LayoutControl::IgnoreLayout`] or [`LayoutControl::Overlay`] are not considered.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct RectrayDraggable;
+
+/// [`Plugin`] for [`RectrayDraggable`].
+#[derive(Debug, Clone, Copy)]
+pub struct RectrayDragPlugin;
+
+impl Plugin for RectrayDragPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RectrayDraggable>();
+        app.add_systems(Update, rectray_drag_reorder);
+    }
+}
+
+/// Reorder dragged [`RectrayDraggable`]s.
+pub fn rectray_drag_reorder(
+    map: Res<RayMap>,
+    mut drag: EventReader<Pointer<Drag>>,
+    draggable: Query<&Parent, With<RectrayDraggable>>,
+    containers: Query<(&RotatedRect, &GlobalTransform), With<Container>>,
+    mut children: Query<&mut Children>,
+    items: Query<(&Transform, Option<&LayoutControl>)>,
+) {
+    for event in drag.read() {
+        let Ok(parent) = draggable.get(event.target) else {
+            continue;
+        };
+        let Ok((rect, global)) = containers.get(parent.get()) else {
+            continue;
+        };
+        let Ok(mut siblings) = children.get_mut(parent.get()) else {
+            continue;
+        };
+        let Some(position) = map
+            .iter()
+            .filter(|(id, _)| id.pointer == event.pointer_id)
+            .filter_map(|(_, ray)| ray_cast_rect(*ray, global, rect))
+            .find(|hit| hit.is_inside())
+            .map(|hit| hit.local())
+        else {
+            continue;
+        };
+        let Some(from) = siblings.iter().position(|x| *x == event.target) else {
+            continue;
+        };
+        // `Transform` is placed on the center of the rect.
+        let to = siblings
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entity)| match items.get(*entity) {
+                Ok((_, Some(LayoutControl::IgnoreLayout | LayoutControl::Overlay))) | Err(_) => {
+                    None
+                }
+                Ok((transform, _)) => Some((index, transform.translation.xy())),
+            })
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+            .map(|(index, _)| index);
+        let Some(to) = to else {
+            continue;
+        };
+        if from < to {
+            (from..to).for_each(|i| siblings.swap(i, i + 1));
+        } else {
+            (to..from).rev().for_each(|i| siblings.swap(i, i + 1));
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Directional focus navigation for keyboard and controller UIs.
+//!
+//! Add [`RectrayFocusPlugin`] and add [`RectrayFocusable`] to widgets.
+//! Send [`RectrayNavigate`] or press arrow keys to move [`RectrayFocused`]
+//! to the nearest focusable widget in that direction in the same [`RectrayFrame`].
+
+use bevy::app::{App, Plugin, Update};
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, EventReader, EventWriter},
+    query::With,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, Resource},
+};
+use bevy::hierarchy::{HierarchyQueryExt, Parent};
+use bevy::input::{keyboard::KeyCode, ButtonInput};
+use bevy::math::Vec2;
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::transform::components::GlobalTransform;
+
+use crate::{world_to_frame_local, RectrayFrame};
+
+/// Cosine of the half angle of the cone a widget must be in to be considered in a direction.
+const CONE_COS: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Prefer widgets furthest in the opposite direction of `dir` and closest to the line of `dir`.
+fn wrap_score(delta: Vec2, dir: Vec2) -> f32 {
+    -delta.dot(dir) - delta.perp_dot(dir).abs()
+}
+
+/// Allow a widget to receive focus from [`RectrayNavigate`].
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct RectrayFocusable;
+
+/// Marks the focused widget, should be on at most one entity.
+///
+/// Inserted and removed by [`rectray_focus_navigation`], can also be inserted manually
+/// to set the initial focus.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct RectrayFocused;
+
+/// Direction of focus navigation, in the local space of a [`RectrayFrame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FocusDirection {
+    /// Unit vector of the direction.
+    pub const fn as_vec(&self) -> Vec2 {
+        match self {
+            FocusDirection::Up => Vec2::Y,
+            FocusDirection::Down => Vec2::NEG_Y,
+            FocusDirection::Left => Vec2::NEG_X,
+            FocusDirection::Right => Vec2::X,
+        }
+    }
+}
+
+/// Move focus in a direction.
+///
+/// If nothing is focused, focus the top left [`RectrayFocusable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct RectrayNavigate(pub FocusDirection);
+
+/// Event sent when [`RectrayFocused`] is moved by [`rectray_focus_navigation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct RectrayFocusChanged {
+    /// The previously focused entity.
+    pub previous: Option<Entity>,
+    /// The newly focused entity.
+    pub focused: Entity,
+}
+
+/// Settings of focus navigation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Resource, Reflect)]
+pub struct RectrayFocusSettings {
+    /// If set and nothing is in the direction, focus the furthest widget in the opposite direction,
+    /// e.g. the first item of a row when moving right from the last item.
+    pub wrap: bool,
+}
+
+/// [`Plugin`] for [`RectrayFocusable`], sends [`RectrayNavigate`] on arrow keys.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RectrayFocusPlugin {
+    /// See [`RectrayFocusSettings::wrap`].
+    pub wrap: bool,
+}
+
+impl Plugin for RectrayFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RectrayFocusable>();
+        app.register_type::<RectrayFocused>();
+        app.insert_resource(RectrayFocusSettings { wrap: self.wrap });
+        app.add_event::<RectrayNavigate>();
+        app.add_event::<RectrayFocusChanged>();
+        app.add_systems(
+            Update,
+            (rectray_keyboard_navigation, rectray_focus_navigation).chain(),
+        );
+    }
+}
+
+/// Send [`RectrayNavigate`] when arrow keys are pressed.
+pub fn rectray_keyboard_navigation(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mut navigate: EventWriter<RectrayNavigate>,
+) {
+    let Some(keys) = keys else {
+        return;
+    };
+    for (key, direction) in [
+        (KeyCode::ArrowUp, FocusDirection::Up),
+        (KeyCode::ArrowDown, FocusDirection::Down),
+        (KeyCode::ArrowLeft, FocusDirection::Left),
+        (KeyCode::ArrowRight, FocusDirection::Right),
+    ] {
+        if keys.just_pressed(key) {
+            navigate.send(RectrayNavigate(direction));
+        }
+    }
+}
+
+/// Move [`RectrayFocused`] on [`RectrayNavigate`].
+///
+/// Candidates are [`RectrayFocusable`]s in the same [`RectrayFrame`] whose centers are within
+/// 45 degrees of the direction, the nearest one is focused.
+pub fn rectray_focus_navigation(
+    mut commands: Commands,
+    settings: Option<Res<RectrayFocusSettings>>,
+    mut navigate: EventReader<RectrayNavigate>,
+    mut changed: EventWriter<RectrayFocusChanged>,
+    focused: Query<Entity, With<RectrayFocused>>,
+    focusable: Query<(Entity, &GlobalTransform), With<RectrayFocusable>>,
+    parents: Query<&Parent>,
+    frames: Query<&GlobalTransform, With<RectrayFrame>>,
+) {
+    let wrap = settings.is_some_and(|x| x.wrap);
+    let mut current = focused.iter().next();
+    let initial = current;
+    // Position of a widget in the local space of its frame.
+    let locate = |entity: Entity| {
+        let (_, global) = focusable.get(entity).ok()?;
+        let frame = parents
+            .iter_ancestors(entity)
+            .find(|parent| frames.contains(*parent))?;
+        let frame_global = frames.get(frame).ok()?;
+        Some((
+            frame,
+            world_to_frame_local(frame_global, global.translation()),
+        ))
+    };
+    for RectrayNavigate(direction) in navigate.read() {
+        let candidates = focusable.iter().filter_map(|(entity, _)| {
+            let (frame, position) = locate(entity)?;
+            Some((entity, frame, position))
+        });
+        let Some((from, frame, origin)) =
+            current.and_then(|e| locate(e).map(|(frame, position)| (e, frame, position)))
+        else {
+            // Focus the top left widget.
+            current = candidates
+                .min_by(|(.., a), (.., b)| (a.x - a.y).total_cmp(&(b.x - b.y)))
+                .map(|(entity, ..)| entity);
+            continue;
+        };
+        let dir = direction.as_vec();
+        let in_cone =
+            |delta: Vec2, dir: Vec2| delta != Vec2::ZERO && delta.normalize().dot(dir) >= CONE_COS;
+        let others: Vec<_> = candidates
+            .filter(|(entity, f, _)| *entity != from && *f == frame)
+            .map(|(entity, _, position)| (entity, position - origin))
+            .collect();
+        let next = others
+            .iter()
+            .filter(|(_, delta)| in_cone(*delta, dir))
+            .min_by(|(_, a), (_, b)| a.length_squared().total_cmp(&b.length_squared()))
+            .or_else(|| {
+                others
+                    .iter()
+                    .filter(|(_, delta)| wrap && in_cone(*delta, -dir))
+                    .max_by(|(_, a), (_, b)| wrap_score(*a, dir).total_cmp(&wrap_score(*b, dir)))
+            })
+            .map(|(entity, _)| *entity);
+        if next.is_some() {
+            current = next;
+        }
+    }
+    if current == initial {
+        return;
+    }
+    let Some(focus) = current else {
+        return;
+    };
+    for entity in focused.iter() {
+        commands.entity(entity).remove::<RectrayFocused>();
+    }
+    commands.entity(focus).insert(RectrayFocused);
+    changed.send(RectrayFocusChanged {
+        previous: initial,
+        focused: focus,
+    });
+}
@@ -1,11 +1,30 @@
 use bevy::ecs::{component::Component, reflect::ReflectComponent};
-use bevy::math::Vec2;
-use bevy::prelude::{Transform, Visibility};
+use bevy::math::{primitives::InfinitePlane3d, Ray3d, Rect, Vec2, Vec3, Vec3Swizzles};
+use bevy::prelude::{GlobalTransform, Transform, Visibility};
 use bevy::reflect::Reflect;
 
-use crate::rect::Anchor;
+use crate::rect::{Anchor, RotatedRect};
+use crate::Transform2D;
+
+/// How the `Transform.scale` of a [`RectrayFrame`] affects its children.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum ScaleMode {
+    /// Children are laid out in `dimension` and scaled with the frame.
+    #[default]
+    Transform,
+    /// Children are laid out in `dimension` multiplied by the frame's `Transform.scale`,
+    /// then translation and scale of direct children are divided by the frame's scale,
+    /// so children fill the scaled frame without being stretched.
+    ///
+    /// [`RotatedRect`](crate::RotatedRect)s of direct children are in the scaled layout space.
+    /// Rotated children are skewed if the scale is not uniform.
+    LayoutSpace,
+}
 
 /// A root node that creates an area to place child entities.
+///
+/// A frame without [`Transform2D`](crate::Transform2D) can be nested under an item of another frame,
+/// its children are then laid out in the rect of that item.
 #[derive(Debug, Default, Reflect, Component)]
 #[reflect(Component)]
 #[require(Transform, Visibility)]
@@ -13,6 +32,21 @@ pub struct RectrayFrame {
     pub dimension: Vec2,
     pub at: Vec2,
     pub z: f32,
+    /// Center of the frame's rect in local space, default is `[0, 0]`.
+    pub center: Vec2,
+    /// How the frame's `Transform.scale` affects its children, default is [`ScaleMode::Transform`].
+    pub scale_mode: ScaleMode,
+    /// If set, `dimension` is set to the size of the bounding box of its children after layout.
+    ///
+    /// The new dimension is used in the next frame, this converges if the children
+    /// do not depend on the dimension of the frame, i.e. a centered dynamic sized [`Container`](crate::layout::Container).
+    /// Children anchored to different sides of the frame keep the frame at its current size.
+    pub fit_content: bool,
+    /// Added to the camera's `order` for hits on descendants of this frame in the picking backend,
+    /// frames with a higher `pick_order` block frames below them, i.e. a modal overlay.
+    ///
+    /// Only the nearest ancestor frame of a pickable is considered.
+    pub pick_order: f32,
 }
 
 impl RectrayFrame {
@@ -21,6 +55,25 @@ impl RectrayFrame {
             dimension,
             at: Vec2::ZERO,
             z: 0.0,
+            center: Vec2::ZERO,
+            scale_mode: ScaleMode::Transform,
+            fit_content: false,
+            pick_order: 0.0,
+        }
+    }
+
+    /// Create a frame from a rect in local space.
+    ///
+    /// For example `Rect::new(0.0, 0.0, width, height)` places `[0, 0]` at the bottom left of the frame.
+    pub fn from_rect(rect: Rect) -> Self {
+        Self {
+            dimension: rect.size(),
+            at: Vec2::ZERO,
+            z: 0.0,
+            center: rect.center(),
+            scale_mode: ScaleMode::Transform,
+            fit_content: false,
+            pick_order: 0.0,
         }
     }
 
@@ -29,11 +82,106 @@ impl RectrayFrame {
             dimension,
             at: anchor.as_vec(),
             z: 0.0,
+            center: Vec2::ZERO,
+            scale_mode: ScaleMode::Transform,
+            fit_content: false,
+            pick_order: 0.0,
         }
     }
 
+    /// Returns the rect of the frame in local space.
+    pub fn rect(&self) -> Rect {
+        Rect::from_center_size(self.center, self.dimension)
+    }
+
     pub const fn with_z(mut self, z: f32) -> Self {
         self.z = z;
         self
     }
+
+    /// Set how the frame's `Transform.scale` affects its children.
+    pub const fn with_scale_mode(mut self, scale_mode: ScaleMode) -> Self {
+        self.scale_mode = scale_mode;
+        self
+    }
+
+    /// Size the frame to the bounding box of its children.
+    pub const fn with_fit_content(mut self) -> Self {
+        self.fit_content = true;
+        self
+    }
+
+    /// Set the picking order of the frame relative to other frames on the same camera.
+    pub const fn with_pick_order(mut self, order: f32) -> Self {
+        self.pick_order = order;
+        self
+    }
+
+    /// Scale of the layout space given the frame's `Transform.scale`,
+    /// `None` if children are not affected.
+    pub(crate) fn layout_scale(&self, transform: &Transform) -> Option<Vec2> {
+        let scale = transform.scale.xy();
+        match self.scale_mode {
+            ScaleMode::Transform => None,
+            ScaleMode::LayoutSpace if scale == Vec2::ONE || scale.cmpeq(Vec2::ZERO).any() => None,
+            ScaleMode::LayoutSpace => Some(scale),
+        }
+    }
+}
+
+/// Convert a point in the local space of a [`RectrayFrame`] to world space.
+pub fn frame_local_to_world(
+    frame_global: &GlobalTransform,
+    frame: &RectrayFrame,
+    local: Vec2,
+) -> Vec3 {
+    frame_global.transform_point(local.extend(frame.z))
+}
+
+/// Project a world space point onto the plane of a [`RectrayFrame`], in the frame's local space.
+pub fn world_to_frame_local(frame_global: &GlobalTransform, world: Vec3) -> Vec2 {
+    frame_global.affine().inverse().transform_point3(world).xy()
+}
+
+/// Intersect a ray with the plane of a [`RectrayFrame`], in the frame's local space.
+///
+/// Returns `None` if the ray does not hit the plane.
+pub fn ray_to_frame_local(frame_global: &GlobalTransform, ray: Ray3d) -> Option<Vec2> {
+    let plane = InfinitePlane3d::new(frame_global.forward());
+    let depth = ray.intersect_plane(frame_global.translation(), plane)?;
+    Some(world_to_frame_local(frame_global, ray.get_point(depth)))
+}
+
+/// Find the world transform of `anchor` on a widget, given the `GlobalTransform` of its parent,
+/// which is usually a [`RectrayFrame`].
+///
+/// The result has the widget's rotation and scale, mirroring the `Transform` produced by the pipeline.
+/// Does not account for [`ScaleMode::LayoutSpace`] on the parent.
+///
+/// ```
+/// # use std::f32::consts::FRAC_PI_2;
+/// # use bevy::prelude::*;
+/// # use bevy_rectray::{world_transform_at, Anchor, RotatedRect, Transform2D};
+/// let frame = GlobalTransform::from(
+///     Transform::from_xyz(100., 0., 0.).with_rotation(Quat::from_rotation_z(FRAC_PI_2)),
+/// );
+/// let transform = Transform2D::UNIT.with_offset(Vec2::new(10., 0.));
+/// let rect = RotatedRect {
+///     center: Vec2::new(10., 0.),
+///     dimension: Vec2::new(4., 2.),
+///     scale: Vec2::ONE,
+///     ..Default::default()
+/// };
+/// let corner = world_transform_at(&frame, &transform, &rect, Anchor::TOP_RIGHT);
+/// assert!(corner.translation().abs_diff_eq(Vec3::new(99., 12., 0.), 1e-4));
+/// ```
+pub fn world_transform_at(
+    parent_global: &GlobalTransform,
+    transform: &Transform2D,
+    rect: &RotatedRect,
+    anchor: Anchor,
+) -> GlobalTransform {
+    let local = rect.transform_at(transform.get_center())
+        * Transform::from_translation((rect.dimension * anchor).extend(0.0));
+    parent_global.mul_transform(local)
 }
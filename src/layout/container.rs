@@ -1,19 +1,20 @@
 use std::ops::{Range, RangeFull, RangeInclusive};
 
-use bevy::ecs::{component::Component, reflect::ReflectComponent};
+use bevy::ecs::{component::Component, entity::Entity, reflect::ReflectComponent, system::Query};
 use bevy::math::Vec2;
 use bevy::prelude::Visibility;
 use bevy::reflect::{std_traits::ReflectDefault, Reflect};
 
-use crate::Transform2D;
+use crate::{RotatedRect, Transform2D};
 
-use super::{LayoutObject, LayoutOutput};
+use super::{LayoutItem, LayoutObject, LayoutOutput};
 
 /// Range of content displayed in the layout, default is `All`.
 ///
 /// This means different things with different layout, could be
 /// entities, rows or pages.
-#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
 pub enum LayoutRange {
     #[default]
     All,
@@ -35,6 +36,22 @@ pub enum LayoutRange {
         step: usize,
         len: usize,
     },
+    /// Display `rows` rows starting from `row` of a grid-like layout,
+    /// behaves like `Bounded` on rows.
+    ///
+    /// For other layouts, same as `Bounded`.
+    GridWindow {
+        row: usize,
+        rows: usize,
+    },
+    /// Display the last `len` items, skipping `offset` items from the end.
+    ///
+    /// Appending items keeps the end visible if `offset` is `0`,
+    /// the maximum value is `offset + len >= total`.
+    Tail {
+        len: usize,
+        offset: usize,
+    },
 }
 
 impl LayoutRange {
@@ -45,22 +62,43 @@ impl LayoutRange {
     pub fn resolve(&mut self, total: usize) {
         match self {
             LayoutRange::All => (),
-            LayoutRange::Bounded { min, len } => {
-                *min = usize::min(*min, total.saturating_sub(*len))
-            }
+            LayoutRange::Bounded { min, len }
+            | LayoutRange::GridWindow {
+                row: min,
+                rows: len,
+            } => *min = usize::min(*min, total.saturating_sub(*len)),
             LayoutRange::Capped { min, .. } => *min = usize::min(*min, total.saturating_sub(1)),
             LayoutRange::Stepped { step, len } => *step = usize::min(*step, total / *len),
+            LayoutRange::Tail { len, offset } => {
+                *offset = usize::min(*offset, total.saturating_sub(*len))
+            }
         }
     }
 
     pub fn to_range(self, total: usize) -> Range<usize> {
         match self {
             LayoutRange::All => 0..total,
-            LayoutRange::Bounded { min, len } => min..(min + len).min(total),
+            LayoutRange::Bounded { min, len }
+            | LayoutRange::GridWindow {
+                row: min,
+                rows: len,
+            } => min..(min + len).min(total),
             LayoutRange::Capped { min, len } => min..(min + len).min(total),
-            LayoutRange::Stepped { step, len } => step * len..(step * len + step).min(total),
+            LayoutRange::Stepped { step, len } => step * len..(step * len + len).min(total),
+            LayoutRange::Tail { len, offset } => {
+                let end = total.saturating_sub(offset);
+                end.saturating_sub(len)..end
+            }
         }
     }
+
+    /// Range of items displayed in a grid of `columns` columns and `total` items,
+    /// where this range operates on rows.
+    pub fn to_flat_range(self, total: usize, columns: usize) -> Range<usize> {
+        let columns = columns.max(1);
+        let rows = self.to_range(total.div_ceil(columns));
+        (rows.start * columns).min(total)..(rows.end * columns).min(total)
+    }
 }
 
 impl From<RangeFull> for LayoutRange {
@@ -87,6 +125,52 @@ impl From<RangeInclusive<usize>> for LayoutRange {
     }
 }
 
+/// Padding on each side of a [`Container`].
+///
+/// Converts from a [`Vec2`] of symmetric horizontal and vertical padding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct Insets {
+    pub top: f32,
+    pub bottom: f32,
+    pub left: f32,
+    pub right: f32,
+}
+
+impl Insets {
+    pub const ZERO: Insets = Insets::splat(0.0);
+
+    pub const fn splat(value: f32) -> Self {
+        Insets {
+            top: value,
+            bottom: value,
+            left: value,
+            right: value,
+        }
+    }
+
+    /// Total horizontal and vertical padding.
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.left + self.right, self.top + self.bottom)
+    }
+
+    /// Offset of the center of the content from the center of the container.
+    pub fn offset(&self) -> Vec2 {
+        Vec2::new(self.left - self.right, self.bottom - self.top) / 2.0
+    }
+}
+
+impl From<Vec2> for Insets {
+    fn from(value: Vec2) -> Self {
+        Insets {
+            top: value.y,
+            bottom: value.y,
+            left: value.x,
+            right: value.x,
+        }
+    }
+}
+
 /// A configurable container that lays out a sequence of Entities.
 #[derive(Debug, Component, Default, Clone, Reflect)]
 #[reflect(Component, Default)]
@@ -98,22 +182,139 @@ pub struct Container {
     /// regardless of layout directions.
     pub margin: Vec2,
     /// Padding around the container.
-    pub padding: Vec2,
+    pub padding: Insets,
     /// If set, only display a subset of children.
     pub range: LayoutRange,
     /// A runtime computed maximum of a layout, could be number of children, lines, pages, etc.
     pub maximum: usize,
+    /// Runtime computed widths of columns for table-like layouts.
+    pub column_widths: Vec<f32>,
+    /// Runtime computed centers and lengths of dividers between items,
+    /// centers are in the same space as anchors of items.
+    pub dividers: Vec<(Vec2, f32)>,
+    /// Runtime computed natural size of the laid out items, excluding padding.
+    ///
+    /// If `content_size` is larger than the [`Dimension`](crate::Dimension) of the container
+    /// on an axis, content overflows the container on that axis.
+    pub content_size: Vec2,
+    /// Runtime computed children passed to the layout, sorted by [`LayoutSortKey`].
+    ///
+    /// Excludes [`LayoutControl::IgnoreLayout`], [`LayoutControl::Overlay`] and skipped children,
+    /// indices of items in `range` correspond to this list.
+    pub items: Vec<Entity>,
+    /// Runtime computed children placed by the layout, in placement order.
+    ///
+    /// Excludes children not in `range` and discarded or ignored children,
+    /// see [`container_children_rects`].
+    pub placed: Vec<Entity>,
+    /// If set, [`set_fac`](Container::set_fac) rounds to the nearest item, line or page
+    /// instead of rounding down.
+    ///
+    /// This only affects rounding, `range` is always a whole index and is clamped
+    /// to `maximum` by the pipeline regardless of this flag.
+    pub snap: bool,
+    /// If set, children with a zero [`Dimension`](crate::Dimension) are treated as
+    /// [`LayoutControl::IgnoreLayout`] until measured, useful for text not yet laid out.
+    ///
+    /// Skipped children do not count towards `maximum`.
+    pub skip_unmeasured: bool,
 }
 
 impl Container {
+    /// Create a container with a layout, other fields are the same as [`Container::default`].
+    ///
+    /// ```
+    /// # use bevy::math::Vec2;
+    /// # use bevy_rectray::layout::{Container, StackLayout};
+    /// let container = Container::new(StackLayout::HSTACK)
+    ///     .with_margin(Vec2::new(4.0, 4.0))
+    ///     .with_padding(Vec2::new(8.0, 2.0))
+    ///     .with_range(0..10);
+    /// assert_eq!(container.padding.size(), Vec2::new(16.0, 4.0));
+    /// ```
+    pub fn new(layout: impl Into<LayoutObject>) -> Self {
+        Container {
+            layout: layout.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the layout.
+    pub fn with_layout(mut self, layout: impl Into<LayoutObject>) -> Self {
+        self.layout = layout.into();
+        self
+    }
+
+    /// Set margin between cells.
+    pub fn with_margin(mut self, margin: Vec2) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Set padding around the container, a [`Vec2`] is treated as symmetric padding.
+    pub fn with_padding(mut self, padding: impl Into<Insets>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Only display a subset of children.
+    pub fn with_range(mut self, range: impl Into<LayoutRange>) -> Self {
+        self.range = range.into();
+        self
+    }
+
+    /// Round scrolling to the nearest item, line or page instead of rounding down.
+    pub fn with_snap(mut self, snap: bool) -> Self {
+        self.snap = snap;
+        self
+    }
+
+    /// Do not lay out children with a zero dimension, see [`Container::skip_unmeasured`].
+    pub fn with_skip_unmeasured(mut self, skip: bool) -> Self {
+        self.skip_unmeasured = skip;
+        self
+    }
+
     pub fn place(&mut self, parent: &LayoutInfo, entities: Vec<super::LayoutItem>) -> LayoutOutput {
         self.layout.place(parent, entities, &mut self.range)
     }
 
+    /// Place items the same way as the `bevy_rectray` pipeline,
+    /// updating `range`, `maximum`, `content_size` and `placed`.
+    ///
+    /// Anchors are scaled to account for padding,
+    /// size of the container is `dimension + padding.size()`.
+    pub fn compute(&mut self, dimension: Vec2, entities: Vec<LayoutItem>) -> LayoutOutput {
+        let margin = self.margin;
+        self.items.clear();
+        self.items.extend(entities.iter().map(|item| item.entity));
+        let mut output = self.place(&LayoutInfo { dimension, margin }, entities);
+        self.maximum = output.max_count;
+        self.column_widths.clone_from(&output.column_widths);
+        self.content_size = output.dimension;
+        self.placed.clear();
+        self.placed
+            .extend(output.entity_anchors.iter().map(|(entity, _)| *entity));
+        let fac = output.dimension / (output.dimension + self.padding.size());
+        // `0 / 0` on an empty axis without padding.
+        let fac = Vec2::select(fac.is_nan_mask(), Vec2::ONE, fac);
+        output
+            .entity_anchors
+            .iter_mut()
+            .for_each(|(_, anc)| *anc *= fac);
+        output.dividers.iter_mut().for_each(|(anc, _)| *anc *= fac);
+        self.dividers.clone_from(&output.dividers);
+        output
+    }
+
     pub fn get_fac(&self) -> f32 {
         match self.range {
             LayoutRange::All => 0.0,
-            LayoutRange::Bounded { min, len } => {
+            LayoutRange::Bounded { min, len }
+            | LayoutRange::GridWindow {
+                row: min,
+                rows: len,
+            } => {
                 if self.maximum <= len {
                     0.0
                 } else {
@@ -135,17 +336,30 @@ impl Container {
                     step as f32 / count as f32
                 }
             }
+            LayoutRange::Tail { len, offset } => {
+                if self.maximum <= len {
+                    1.0
+                } else {
+                    1.0 - offset as f32 / (self.maximum - len) as f32
+                }
+            }
         }
         .clamp(0.0, 1.0)
     }
 
     pub fn set_fac(&mut self, fac: f32) {
         let fac = fac.clamp(0.0, 1.0);
+        let snap = self.snap;
+        let to_index = |x: f32| if snap { x.round() as usize } else { x as usize };
         match &mut self.range {
             LayoutRange::All => (),
-            LayoutRange::Bounded { min, len } => {
+            LayoutRange::Bounded { min, len }
+            | LayoutRange::GridWindow {
+                row: min,
+                rows: len,
+            } => {
                 if self.maximum > *len {
-                    *min = ((self.maximum - *len) as f32 * fac) as usize
+                    *min = to_index((self.maximum - *len) as f32 * fac)
                 } else {
                     *min = 0
                 }
@@ -154,7 +368,7 @@ impl Container {
                 if self.maximum == 0 {
                     *min = 0
                 } else {
-                    *min = (self.maximum as f32 * fac) as usize
+                    *min = to_index(self.maximum as f32 * fac)
                 }
             }
             LayoutRange::Stepped { step, len } => {
@@ -162,18 +376,122 @@ impl Container {
                 if count == 0 {
                     *step = 0
                 } else {
-                    *step = (count as f32 * fac) as usize
+                    *step = to_index(count as f32 * fac)
+                }
+            }
+            LayoutRange::Tail { len, offset } => {
+                if self.maximum > *len {
+                    *offset = to_index((self.maximum - *len) as f32 * (1.0 - fac))
+                } else {
+                    *offset = 0
                 }
             }
         }
     }
 
+    /// Index of `entity` in [`items`](Container::items), i.e. in layout order.
+    ///
+    /// Returns `None` if `entity` is not laid out by this container.
+    pub fn index_of(&self, entity: Entity) -> Option<usize> {
+        self.items.iter().position(|x| *x == entity)
+    }
+
+    /// Page or step `index` falls on, where each page contains `len` items, lines or pages
+    /// of the current [`LayoutRange`].
+    ///
+    /// Always `0` for `LayoutRange::All`.
+    pub fn page_of(&self, index: usize) -> usize {
+        match self.range {
+            LayoutRange::All => 0,
+            LayoutRange::Bounded { len, .. }
+            | LayoutRange::GridWindow { rows: len, .. }
+            | LayoutRange::Capped { len, .. }
+            | LayoutRange::Stepped { len, .. }
+            | LayoutRange::Tail { len, .. } => index.checked_div(len).unwrap_or(0),
+        }
+    }
+
+    /// Modify `range` so that `index` is visible, clamped by `maximum`.
+    ///
+    /// `Bounded` and `Capped` ranges move the minimum amount required,
+    /// `Stepped` ranges move to the step containing `index`.
+    pub fn scroll_to_index(&mut self, index: usize) {
+        match &mut self.range {
+            LayoutRange::All => (),
+            LayoutRange::Bounded { min, len }
+            | LayoutRange::Capped { min, len }
+            | LayoutRange::GridWindow {
+                row: min,
+                rows: len,
+            } => {
+                if index < *min {
+                    *min = index;
+                } else if index >= *min + *len {
+                    *min = (index + 1).saturating_sub(*len);
+                }
+            }
+            LayoutRange::Stepped { step, len } => {
+                *step = index.checked_div(*len).unwrap_or(0);
+            }
+            LayoutRange::Tail { len, offset } => {
+                let end = self.maximum.saturating_sub(*offset);
+                if index >= end {
+                    *offset = self.maximum.saturating_sub(index + 1);
+                } else if index + *len < end {
+                    *offset = self.maximum.saturating_sub(index + *len);
+                }
+            }
+        }
+        self.range.resolve(self.maximum);
+    }
+
+    /// Move `range` to start on the `page`-th page of `len` items, clamped by `maximum`.
+    pub fn set_page(&mut self, page: usize) {
+        match &mut self.range {
+            LayoutRange::All => (),
+            LayoutRange::Bounded { min, len }
+            | LayoutRange::Capped { min, len }
+            | LayoutRange::GridWindow {
+                row: min,
+                rows: len,
+            } => {
+                *min = page * *len;
+            }
+            LayoutRange::Stepped { step, .. } => *step = page,
+            LayoutRange::Tail { len, offset } => {
+                *offset = self.maximum.saturating_sub(*len + page * *len);
+            }
+        }
+        self.range.resolve(self.maximum);
+    }
+
+    /// Fraction of items, lines or pages visible under the current [`LayoutRange`], in `[0, 1]`.
+    pub fn visible_fraction(&self) -> f32 {
+        let len = match self.range {
+            LayoutRange::All => return 1.0,
+            LayoutRange::Bounded { len, .. } | LayoutRange::GridWindow { rows: len, .. } => len,
+            LayoutRange::Capped { len, .. } => len,
+            LayoutRange::Stepped { len, .. } => len,
+            LayoutRange::Tail { len, .. } => len,
+        };
+        if self.maximum == 0 {
+            1.0
+        } else {
+            (len as f32 / self.maximum as f32).min(1.0)
+        }
+    }
+
     pub fn decrement(&mut self) {
         match &mut self.range {
             LayoutRange::All => (),
-            LayoutRange::Bounded { min, .. } => *min = min.saturating_sub(1),
+            LayoutRange::Bounded { min, .. } | LayoutRange::GridWindow { row: min, .. } => {
+                *min = min.saturating_sub(1)
+            }
             LayoutRange::Capped { min, .. } => *min = min.saturating_sub(1),
             LayoutRange::Stepped { step, .. } => *step = step.saturating_sub(1),
+            LayoutRange::Tail { offset, .. } => {
+                *offset += 1;
+            }
         }
     }
 
@@ -181,7 +499,7 @@ impl Container {
         // range doesn't matter since this will be resolved in `pipeline`.
         match &mut self.range {
             LayoutRange::All => (),
-            LayoutRange::Bounded { min, .. } => {
+            LayoutRange::Bounded { min, .. } | LayoutRange::GridWindow { row: min, .. } => {
                 *min += 1;
             }
             LayoutRange::Capped { min, .. } => {
@@ -190,10 +508,33 @@ impl Container {
             LayoutRange::Stepped { step, .. } => {
                 *step += 1;
             }
+            LayoutRange::Tail { offset, .. } => *offset = offset.saturating_sub(1),
         }
     }
 }
 
+/// Compute a layout without the ECS, matches the output of the `bevy_rectray` pipeline.
+///
+/// `dimension` is the `Dimension` of the container,
+/// See [`Container::compute`] for details.
+pub fn compute(container: &Container, dimension: Vec2, items: &[LayoutItem]) -> LayoutOutput {
+    container.clone().compute(dimension, items.to_vec())
+}
+
+/// Iterate over children placed by a [`Container`] and their [`RotatedRect`]s, in placement order.
+///
+/// This reflects the last computation of the pipeline, i.e. the previous frame
+/// if called before [`RectrayTransformSet`](crate::RectrayTransformSet).
+pub fn container_children_rects<'a>(
+    container: &'a Container,
+    rects: &'a Query<'_, '_, &'static RotatedRect>,
+) -> impl Iterator<Item = (Entity, &'a RotatedRect)> + 'a {
+    container
+        .placed
+        .iter()
+        .filter_map(|entity| Some((*entity, rects.get(*entity).ok()?)))
+}
+
 /// Dimension info of a layout parent.
 pub struct LayoutInfo {
     pub dimension: Vec2,
@@ -219,9 +560,60 @@ pub enum LayoutControl {
     /// The item is considered discarded and its children will not be updated.
     LinebreakMarker,
     /// For `compact`, `span` and `paragraph`, trim WhiteSpace at the beginning and end of each row.
+    /// `compact` also collapses consecutive WhiteSpace into the first one.
     ///
     /// The item is considered discarded and its children will not be updated.
     WhiteSpace,
+    /// For `span` and `flex`, take no intrinsic space and absorb leftover space on the main axis,
+    /// split between spacers in a row by their [`FlexGrow`], or `1` if not specified.
+    ///
+    /// The item is considered discarded and its children will not be updated.
+    Spacer,
+    /// Use default anchor based positioning like [`IgnoreLayout`](LayoutControl::IgnoreLayout),
+    /// but grow the container to at least the size of this item plus its offset.
+    ///
+    /// Useful for badges pinned to a corner of a container.
+    ///
+    /// Other items stay in place when the container grows, including with padding.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy::ecs::event::Events;
+    /// # use bevy_rectray::{*, layout::*};
+    /// fn layout(overlay: bool) -> (Vec2, Vec3) {
+    ///     let mut world = World::new();
+    ///     world.init_resource::<Events<RectrayLayoutChanged>>();
+    ///     let frame = world.spawn(RectrayFrame::from_dimension(Vec2::splat(100.0))).id();
+    ///     let mut container = Container::new(StackLayout::HSTACK);
+    ///     container.padding = Insets { left: 10.0, right: 2.0, top: 4.0, bottom: 6.0 };
+    ///     let container = world.spawn((container, Dimension(Vec2::ZERO))).id();
+    ///     let item = world.spawn((Transform2D::UNIT, Dimension(Vec2::new(10.0, 10.0)))).id();
+    ///     world.entity_mut(frame).add_children(&[container]);
+    ///     world.entity_mut(container).add_children(&[item]);
+    ///     if overlay {
+    ///         let badge = world.spawn((
+    ///             Transform2D::UNIT.with_anchor(Anchor::TOP_RIGHT),
+    ///             Dimension(Vec2::new(40.0, 30.0)),
+    ///             LayoutControl::Overlay,
+    ///         )).id();
+    ///         world.entity_mut(container).add_children(&[badge]);
+    ///     }
+    ///     let mut schedule = Schedule::default();
+    ///     schedule.add_systems(compute_transform_2d);
+    ///     schedule.run(&mut world);
+    ///     (
+    ///         world.get::<RotatedRect>(container).unwrap().dimension,
+    ///         world.get::<Transform>(item).unwrap().translation,
+    ///     )
+    /// }
+    /// let (size, item) = layout(false);
+    /// let (grown, moved) = layout(true);
+    /// // Content box grows to the badge, padding is added on top.
+    /// assert_eq!(grown, Vec2::new(40.0, 30.0) + Vec2::new(12.0, 10.0));
+    /// assert!(grown.cmpgt(size).all());
+    /// assert!(moved.abs_diff_eq(item, 1e-4));
+    /// ```
+    Overlay,
 }
 
 impl LayoutControl {
@@ -233,3 +625,172 @@ impl LayoutControl {
         )
     }
 }
+
+/// Portion of the leftover space taken by this item in a [`FlexLayout`](super::FlexLayout), default is `0`.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq)]
+#[reflect(Component, Default)]
+pub struct FlexGrow(pub f32);
+
+/// Portion of the overflowing space removed from this item in a [`FlexLayout`](super::FlexLayout), default is `1`.
+#[derive(Debug, Clone, Copy, Component, Reflect, PartialEq)]
+#[reflect(Component, Default)]
+pub struct FlexShrink(pub f32);
+
+impl Default for FlexShrink {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Number of cells taken by an item in a [`GridLayout`](super::GridLayout), default is `1x1`.
+///
+/// Following items skip occupied cells, an item that does not fit in the remaining
+/// columns of a row is moved to the next row. `0` is treated as `1`
+/// and `cols` is capped at the number of columns.
+#[derive(Debug, Clone, Copy, Component, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct GridSpan {
+    pub cols: usize,
+    pub rows: usize,
+}
+
+impl GridSpan {
+    pub const ONE: GridSpan = GridSpan::new(1, 1);
+
+    pub const fn new(cols: usize, rows: usize) -> Self {
+        GridSpan { cols, rows }
+    }
+}
+
+impl Default for GridSpan {
+    fn default() -> Self {
+        Self::ONE
+    }
+}
+
+/// Text direction of an item, used by [`Bidi::Auto`](super::Bidi::Auto), default is `Ltr`.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub enum TextDirection {
+    /// Left to right.
+    #[default]
+    Ltr,
+    /// Right to left.
+    Rtl,
+}
+
+/// Distance from the bottom of an item to its text baseline,
+/// items with a baseline in the same line of a [`ParagraphLayout`](super::ParagraphLayout)
+/// are aligned by their baselines.
+///
+/// For vertical paragraphs, this is the distance from the left of the item.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq)]
+#[reflect(Component, Default)]
+pub struct TextBaseline(pub f32);
+
+/// Sort key of an item in a [`Container`].
+///
+/// If any child of a container has a `LayoutSortKey`, children are stably sorted
+/// by their keys before being laid out, children without a key use `0`.
+/// This does not modify the order of `Children`.
+///
+/// [`LayoutRange`] applies after sorting. Children with [`LayoutControl::IgnoreLayout`]
+/// are positioned independently and are not affected.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Component, Default)]
+pub struct LayoutSortKey(pub i64);
+
+/// Hide children of this [`Container`] that are not placed by the layout,
+/// i.e. outside of its [`LayoutRange`] or discarded.
+///
+/// Sets `Visibility` of such children to `Hidden`, and placed children to `Inherited`,
+/// so `Visibility` of children should not be managed elsewhere.
+/// Hidden children are set to `Inherited` when this is removed.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct RectrayCull;
+
+/// Classify children of this [`Container`] by whether they are visible in the container's rect,
+/// the result is written to [`ClipState`] on each child.
+/// [`ClipState`] is removed from children when this is removed.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct RectrayClip;
+
+/// Runtime computed visibility of an item in the rect of a [`RectrayClip`] container,
+/// inserted by the `bevy_rectray` pipeline.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub enum ClipState {
+    /// Entirely inside the container.
+    #[default]
+    Inside,
+    /// Partially overlaps the container.
+    Partial,
+    /// Entirely outside the container.
+    Outside,
+}
+
+impl ClipState {
+    /// Classify `rect` against `clip`, both in the same space.
+    pub fn classify(clip: &RotatedRect, rect: &RotatedRect) -> Self {
+        if rect.corners().iter().all(|x| clip.contains_point(*x)) {
+            ClipState::Inside
+        } else if clip.intersects(rect) {
+            ClipState::Partial
+        } else {
+            ClipState::Outside
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::StackLayout;
+
+    #[test]
+    fn stepped_range() {
+        let mut range = LayoutRange::Stepped { step: 1, len: 4 };
+        range.resolve(10);
+        assert_eq!(range.to_range(10), 4..8);
+        let mut range = LayoutRange::Stepped { step: 5, len: 4 };
+        range.resolve(10);
+        assert_eq!(range.to_range(10), 8..10);
+    }
+
+    /// Indices of items placed by a stack of `count` items.
+    fn placed(container: &mut Container, count: u32) -> Vec<u32> {
+        let items = (0..count)
+            .map(|i| LayoutItem::new(Entity::from_raw(i), Vec2::ZERO, Vec2::ONE))
+            .collect();
+        container.compute(Vec2::ZERO, items);
+        container.placed.iter().map(|e| e.index()).collect()
+    }
+
+    #[test]
+    fn tail_range() {
+        let mut range = LayoutRange::Tail { len: 3, offset: 10 };
+        range.resolve(5);
+        assert_eq!(range, LayoutRange::Tail { len: 3, offset: 2 });
+        assert_eq!(range.to_range(5), 0..3);
+        assert_eq!(LayoutRange::Tail { len: 3, offset: 0 }.to_range(2), 0..2);
+    }
+
+    #[test]
+    fn tail_follows_appends() {
+        let mut container =
+            Container::new(StackLayout::VSTACK).with_range(LayoutRange::Tail { len: 3, offset: 0 });
+        assert_eq!(placed(&mut container, 5), vec![2, 3, 4]);
+        assert_eq!(container.get_fac(), 1.0);
+        assert_eq!(placed(&mut container, 6), vec![3, 4, 5]);
+        // Scrolled up by one, the window stays one item above the end.
+        container.decrement();
+        assert_eq!(placed(&mut container, 6), vec![2, 3, 4]);
+        container.increment();
+        assert_eq!(placed(&mut container, 7), vec![4, 5, 6]);
+        container.set_fac(0.0);
+        assert_eq!(placed(&mut container, 7), vec![0, 1, 2]);
+        assert_eq!(container.get_fac(), 0.0);
+    }
+}
@@ -1,3 +1,4 @@
+use std::f32::consts::TAU;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -5,10 +6,11 @@ use std::ops::Deref;
 use bevy::ecs::entity::Entity;
 use bevy::math::Vec2;
 use bevy::reflect::std_traits::ReflectDefault;
-use bevy::reflect::Reflect;
+use bevy::reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use downcast_rs::{impl_downcast, Downcast};
+use serde::{Deserialize, Serialize};
 
-use super::{util::*, LayoutInfo, LayoutRange};
+use super::{util::*, LayoutControl, LayoutInfo, LayoutRange};
 
 // asserts layout is object safe
 const _: Option<Box<dyn Layout>> = None;
@@ -38,9 +40,13 @@ pub trait Layout: Downcast + Debug + Send + Sync + 'static {
 impl_downcast!(Layout);
 
 /// Type erased [`Layout`].
+///
+/// Serialized by the name of the layout in [`LayoutRegistry`](super::LayoutRegistry),
+/// reflected as an opaque value.
 #[derive(Debug, Reflect)]
-#[reflect(Default)]
-pub struct LayoutObject(#[reflect(ignore)] Box<dyn Layout>);
+#[reflect(opaque)]
+#[reflect(Default, Serialize, Deserialize)]
+pub struct LayoutObject(pub(crate) Box<dyn Layout>);
 
 impl Default for LayoutObject {
     fn default() -> Self {
@@ -85,12 +91,18 @@ impl Deref for LayoutObject {
 }
 
 /// Output of a layout, containing anchors of entities, and the computed dimension of the layout.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct LayoutOutput {
     pub entity_anchors: Vec<(Entity, Vec2)>,
     pub dimension: Vec2,
     /// Maximum value for the layout.
     pub max_count: usize,
+    /// If set, overrides `Dimension` of these entities.
+    pub entity_dimensions: Vec<(Entity, Vec2)>,
+    /// Widths of columns for table-like layouts, empty otherwise.
+    pub column_widths: Vec<f32>,
+    /// Centers and lengths of dividers between items, centers are normalized like anchors.
+    pub dividers: Vec<(Vec2, f32)>,
 }
 
 impl LayoutOutput {
@@ -98,6 +110,9 @@ impl LayoutOutput {
         self.entity_anchors
             .iter_mut()
             .for_each(|(_, x)| *x = *x / self.dimension - 0.5);
+        self.dividers
+            .iter_mut()
+            .for_each(|(x, _)| *x = *x / self.dimension - 0.5);
         self
     }
     pub fn with_max(mut self, max: usize) -> Self {
@@ -108,7 +123,7 @@ impl LayoutOutput {
 
 /// A dynamic dimensioned layout with size equal
 /// to the maximum of its children and no additional behaviors.
-#[derive(Debug, Clone, Copy, Reflect)]
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
 pub struct BoundsLayout {
     /// If set, use `Dimension` on that axis.
     pub fixed: [bool; 2],
@@ -116,6 +131,8 @@ pub struct BoundsLayout {
     pub min: Vec2,
     /// Maximum bounds.
     pub max: Vec2,
+    /// Dimension if no items are displayed, not affected by `min` and `max`.
+    pub empty: Vec2,
 }
 
 impl BoundsLayout {
@@ -124,6 +141,7 @@ impl BoundsLayout {
         fixed: [false; 2],
         min: Vec2::ZERO,
         max: Vec2::MAX,
+        empty: Vec2::ZERO,
     };
 
     pub const fn from_max(max: Vec2) -> Self {
@@ -131,6 +149,7 @@ impl BoundsLayout {
             fixed: [false; 2],
             min: Vec2::MAX,
             max,
+            empty: Vec2::ZERO,
         }
     }
 
@@ -139,14 +158,22 @@ impl BoundsLayout {
             fixed: [false; 2],
             min,
             max: Vec2::MAX,
+            empty: Vec2::ZERO,
         }
     }
 
+    /// Set the dimension if no items are displayed.
+    pub const fn with_empty(mut self, empty: Vec2) -> Self {
+        self.empty = empty;
+        self
+    }
+
     pub const fn x_bounds(min: f32, max: f32) -> Self {
         BoundsLayout {
             fixed: [false, true],
             min: Vec2::splat(min),
             max: Vec2::splat(max),
+            empty: Vec2::ZERO,
         }
     }
 
@@ -155,6 +182,7 @@ impl BoundsLayout {
             fixed: [true, false],
             min: Vec2::splat(min),
             max: Vec2::splat(max),
+            empty: Vec2::ZERO,
         }
     }
 }
@@ -185,7 +213,11 @@ impl Layout for BoundsLayout {
         let min = self.min;
         let max = self.max;
 
-        let dim = max_dim.clamp(min, max);
+        let dim = if entity_anchors.is_empty() {
+            self.empty
+        } else {
+            max_dim.clamp(min, max)
+        };
 
         let dimension = Vec2::new(
             if !self.fixed[0] {
@@ -203,6 +235,72 @@ impl Layout for BoundsLayout {
             entity_anchors,
             dimension,
             max_count: entities.len(),
+            ..Default::default()
+        }
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+}
+
+/// A layout that positions items by their [`Transform2D`](crate::Transform2D) only,
+/// like [`LayoutControl::IgnoreLayout`](super::LayoutControl::IgnoreLayout) on every item.
+///
+/// On axes that are not `fixed`, the dimension is the smallest that contains
+/// every item including offsets, ignoring rotation and scale.
+/// Items anchored to an edge with offsets pointing outwards cannot be contained.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct AbsoluteLayout {
+    /// If set, use `Dimension` on that axis.
+    pub fixed: [bool; 2],
+}
+
+impl AbsoluteLayout {
+    /// Size the container to contain its items.
+    pub const FIT: Self = Self { fixed: [false; 2] };
+    /// Use `Dimension` of the container.
+    pub const FIXED: Self = Self { fixed: [true; 2] };
+}
+
+impl Layout for AbsoluteLayout {
+    fn place(
+        &self,
+        info: &LayoutInfo,
+        entities: Vec<LayoutItem>,
+        range: &mut LayoutRange,
+    ) -> LayoutOutput {
+        range.resolve(entities.len());
+        let items = &entities[range.to_range(entities.len())];
+        // Solve for the smallest `dim` where `dim * (0.5 - anchor) >= right`
+        // and `dim * (0.5 + anchor) >= left` for each item, relative to the anchor point.
+        let mut bounds = Vec2::ZERO;
+        for item in items {
+            let right = item.offset + (0.5 - item.own_anchor) * item.dimension;
+            let left = (0.5 + item.own_anchor) * item.dimension - item.offset;
+            for (fac, len) in [(0.5 - item.anchor, right), (0.5 + item.anchor, left)] {
+                let min = len / fac;
+                let min = Vec2::select(fac.cmpgt(Vec2::ZERO), min, Vec2::ZERO);
+                bounds = bounds.max(min);
+            }
+        }
+        let dimension = Vec2::new(
+            if self.fixed[0] {
+                info.dimension.x
+            } else {
+                bounds.x
+            },
+            if self.fixed[1] {
+                info.dimension.y
+            } else {
+                bounds.y
+            },
+        );
+        LayoutOutput {
+            entity_anchors: items.iter().map(|x| (x.entity, x.anchor)).collect(),
+            dimension,
+            max_count: entities.len(),
+            ..Default::default()
         }
     }
 
@@ -212,8 +310,27 @@ impl Layout for BoundsLayout {
 }
 
 /// A size agnostic mono-directional container.
-#[derive(Debug, Default, Reflect)]
-pub struct StackLayout<D: Direction = X>(#[reflect(ignore)] PhantomData<D>);
+#[derive(Debug, Reflect, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct StackLayout<D: Direction = X> {
+    /// Alignment of items on the cross axis.
+    pub cross_align: Align,
+    /// If set, place items in reverse order, regardless of direction.
+    ///
+    /// [`LayoutRange`] still operates on the original order.
+    pub reversed: bool,
+    /// If set, reserve space of this thickness between items for a divider,
+    /// positions of dividers are written to [`Container::dividers`](super::Container::dividers).
+    pub separator: Option<f32>,
+    /// If set, scale items on the main axis in proportion to their dimensions,
+    /// so that items, margins and separators take exactly this length.
+    ///
+    /// Makes the layout no longer size agnostic.
+    pub fill: Option<f32>,
+    #[reflect(ignore)]
+    #[serde(skip)]
+    p: PhantomData<D>,
+}
 
 impl<D: Direction> Copy for StackLayout<D> {}
 impl<D: Direction> Clone for StackLayout<D> {
@@ -222,22 +339,77 @@ impl<D: Direction> Clone for StackLayout<D> {
     }
 }
 
+impl<D: Direction> Default for StackLayout<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StackLayout {
     /// A left to right layout.
-    pub const HSTACK: StackLayout<X> = StackLayout(PhantomData);
+    pub const HSTACK: StackLayout<X> = StackLayout::new();
     /// A top to bottom layout.
-    pub const VSTACK: StackLayout<Rev<Y>> = StackLayout(PhantomData);
+    pub const VSTACK: StackLayout<Rev<Y>> = StackLayout::new();
 }
 
 impl<D: Direction> StackLayout<D> {
-    pub fn new() -> Self {
-        StackLayout(PhantomData)
+    pub const fn new() -> Self {
+        StackLayout {
+            cross_align: Align::Inherit,
+            reversed: false,
+            separator: None,
+            fill: None,
+            p: PhantomData,
+        }
+    }
+
+    /// Set alignment of items on the cross axis.
+    pub const fn with_cross_align(mut self, align: Align) -> Self {
+        self.cross_align = align;
+        self
+    }
+
+    /// Place items in reverse order.
+    pub const fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Reserve space of `thickness` between items for a divider.
+    pub const fn with_separator(mut self, thickness: f32) -> Self {
+        self.separator = Some(thickness);
+        self
+    }
+
+    /// Scale items on the main axis to fill `len`.
+    pub const fn with_fill(mut self, len: f32) -> Self {
+        self.fill = Some(len);
+        self
     }
 }
 
 /// A fix-sized mono-directional container.
-#[derive(Debug, Default, Reflect)]
-pub struct SpanLayout<D: StretchDir = X>(#[reflect(ignore)] PhantomData<D>);
+///
+/// With [`Justify::Inherit`], items are grouped by the [`bucket`](crate::Anchor::bucket)
+/// of their anchors on the main axis, items in the start, center and end groups are packed
+/// to the start, center and end of the span respectively, like sections of a toolbar.
+#[derive(Debug, Reflect, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SpanLayout<D: StretchDir = X> {
+    /// Alignment of items on the cross axis.
+    pub cross_align: Align,
+    /// Distribution of leftover space on the main axis.
+    ///
+    /// If not `Inherit`, items are no longer grouped by anchors and stretch is ignored.
+    pub justify: Justify,
+    /// If set, place items in reverse order, regardless of direction.
+    ///
+    /// [`LayoutRange`] still operates on the original order.
+    pub reversed: bool,
+    #[reflect(ignore)]
+    #[serde(skip)]
+    p: PhantomData<D>,
+}
 
 impl<D: StretchDir> Copy for SpanLayout<D> {}
 impl<D: StretchDir> Clone for SpanLayout<D> {
@@ -246,31 +418,182 @@ impl<D: StretchDir> Clone for SpanLayout<D> {
     }
 }
 
+impl<D: StretchDir> Default for SpanLayout<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SpanLayout {
     /// A left to right layout with fixed dimension.
-    pub const HBOX: SpanLayout<X> = SpanLayout(PhantomData);
+    pub const HBOX: SpanLayout<X> = SpanLayout::new();
     /// A top to bottom layout with fixed dimension.
-    pub const VBOX: SpanLayout<Rev<Y>> = SpanLayout(PhantomData);
+    pub const VBOX: SpanLayout<Rev<Y>> = SpanLayout::new();
 }
 
 impl<D: StretchDir> SpanLayout<D> {
+    pub const fn new() -> Self {
+        SpanLayout {
+            cross_align: Align::Inherit,
+            justify: Justify::Inherit,
+            reversed: false,
+            p: PhantomData,
+        }
+    }
+
+    pub const fn with_stretch(self) -> SpanLayout<Stretch<D>> {
+        SpanLayout {
+            cross_align: self.cross_align,
+            justify: self.justify,
+            reversed: self.reversed,
+            p: PhantomData,
+        }
+    }
+
+    /// Set alignment of items on the cross axis.
+    pub const fn with_cross_align(mut self, align: Align) -> Self {
+        self.cross_align = align;
+        self
+    }
+
+    /// Set distribution of leftover space on the main axis.
+    pub const fn with_justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Place items in reverse order.
+    pub const fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+}
+
+/// A fix-sized mono-directional container that resizes its children
+/// to fill its dimension on the main axis.
+///
+/// Leftover space is distributed by [`FlexGrow`](super::FlexGrow),
+/// overflowing space is removed by [`FlexShrink`](super::FlexShrink).
+#[derive(Debug, Default, Reflect, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FlexLayout<D: Direction = X>(
+    #[reflect(ignore)]
+    #[serde(skip)]
+    PhantomData<D>,
+);
+
+impl<D: Direction> Copy for FlexLayout<D> {}
+impl<D: Direction> Clone for FlexLayout<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl FlexLayout {
+    /// A left to right layout that fills its dimension.
+    pub const HFLEX: FlexLayout<X> = FlexLayout(PhantomData);
+    /// A top to bottom layout that fills its dimension.
+    pub const VFLEX: FlexLayout<Rev<Y>> = FlexLayout(PhantomData);
+}
+
+impl<D: Direction> FlexLayout<D> {
     pub fn new() -> Self {
-        SpanLayout(PhantomData)
+        FlexLayout(PhantomData)
+    }
+}
+
+/// A dynamic sized single line layout that trims [`LayoutControl::WhiteSpace`](super::LayoutControl::WhiteSpace)
+/// at the start and end of the line and collapses consecutive whitespace into the first one,
+/// the single line analogue of [`ParagraphLayout`].
+#[derive(Debug, Default, Reflect, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CompactLayout<D: Direction = X>(
+    #[reflect(ignore)]
+    #[serde(skip)]
+    PhantomData<D>,
+);
+
+impl<D: Direction> Copy for CompactLayout<D> {}
+impl<D: Direction> Clone for CompactLayout<D> {
+    fn clone(&self) -> Self {
+        *self
     }
+}
+
+impl CompactLayout {
+    /// A left to right compact layout.
+    pub const HCOMPACT: CompactLayout<X> = CompactLayout(PhantomData);
+    /// A top to bottom compact layout.
+    pub const VCOMPACT: CompactLayout<Rev<Y>> = CompactLayout(PhantomData);
+}
 
-    pub fn with_stretch(self) -> SpanLayout<Stretch<D>> {
-        SpanLayout(PhantomData)
+impl<D: Direction> CompactLayout<D> {
+    pub fn new() -> Self {
+        CompactLayout(PhantomData)
     }
 }
 
-/// A multiline version of the `span` layout, similar to the layout of a paragraph.
-#[derive(Debug, Default, Reflect)]
-pub struct ParagraphLayout<D1: StretchDir = X, D2: Direction = Rev<Y>>(
-    #[reflect(ignore)] PhantomData<(D1, D2)>,
+/// A multiline layout that wraps items to the next line when exceeding its dimension,
+/// similar to [`ParagraphLayout`] but without special handling of whitespace.
+///
+/// [`LayoutRange`] operates on lines.
+#[derive(Debug, Reflect, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct WrapLayout<D1: Direction = X, D2: Direction = Rev<Y>>(
+    #[reflect(ignore)]
+    #[serde(skip)]
+    PhantomData<(D1, D2)>,
 )
 where
     (D1, D2): DirectionPair;
 
+impl<D1: Direction, D2: Direction> Copy for WrapLayout<D1, D2> where (D1, D2): DirectionPair {}
+impl<D1: Direction, D2: Direction> Clone for WrapLayout<D1, D2>
+where
+    (D1, D2): DirectionPair,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D1: Direction, D2: Direction> Default for WrapLayout<D1, D2>
+where
+    (D1, D2): DirectionPair,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WrapLayout {
+    /// A left to right, top to bottom wrapping layout.
+    pub const WRAP: Self = Self(PhantomData);
+}
+
+impl<D1: Direction, D2: Direction> WrapLayout<D1, D2>
+where
+    (D1, D2): DirectionPair,
+{
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// A multiline version of the `span` layout, similar to the layout of a paragraph.
+#[derive(Debug, Reflect, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ParagraphLayout<D1: StretchDir = X, D2: Direction = Rev<Y>>
+where
+    (D1, D2): DirectionPair,
+{
+    /// Direction of items in each line.
+    pub bidi: Bidi,
+    #[reflect(ignore)]
+    #[serde(skip)]
+    p: PhantomData<(D1, D2)>,
+}
+
 impl<D1: StretchDir, D2: Direction> Copy for ParagraphLayout<D1, D2> where (D1, D2): DirectionPair {}
 impl<D1: StretchDir, D2: Direction> Clone for ParagraphLayout<D1, D2>
 where
@@ -281,23 +604,600 @@ where
     }
 }
 
+impl<D1: StretchDir, D2: Direction> Default for ParagraphLayout<D1, D2>
+where
+    (D1, D2): DirectionPair,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ParagraphLayout {
     /// A left to right, top to bottom paragraph, similar to the default layout of a webpage.
-    pub const PARAGRAPH: Self = Self(PhantomData);
+    pub const PARAGRAPH: Self = Self::new();
 }
 
 impl<D1: StretchDir, D2: Direction> ParagraphLayout<D1, D2>
 where
     (D1, D2): DirectionPair,
 {
-    pub fn new() -> Self {
-        Self(PhantomData)
+    pub const fn new() -> Self {
+        ParagraphLayout {
+            bidi: Bidi::Ltr,
+            p: PhantomData,
+        }
     }
 
     pub fn with_stretch(self) -> ParagraphLayout<Stretch<D1>, D2>
     where
         (Stretch<D1>, D2): DirectionPair,
     {
-        ParagraphLayout::<Stretch<D1>, D2>(PhantomData)
+        ParagraphLayout {
+            bidi: self.bidi,
+            p: PhantomData,
+        }
+    }
+
+    /// Set direction of items in each line.
+    pub const fn with_bidi(mut self, bidi: Bidi) -> Self {
+        self.bidi = bidi;
+        self
+    }
+}
+
+/// Cells taken by an item in a grid.
+#[derive(Debug, Clone, Copy)]
+struct GridCell {
+    x: usize,
+    y: usize,
+    cols: usize,
+    rows: usize,
+}
+
+/// Cells of items in a grid of `columns` columns, `None` for [`LinebreakMarker`](LayoutControl::LinebreakMarker)s.
+///
+/// Items are placed left to right, top to bottom, in the first free cells
+/// after the previous item that fit their [`GridSpan`](super::GridSpan) if `spans` is set.
+/// A [`Linebreak`](LayoutControl::Linebreak) or [`LinebreakMarker`](LayoutControl::LinebreakMarker)
+/// moves the following items to the next row, unless already at the start of a row.
+fn grid_cells(entities: &[LayoutItem], columns: usize, spans: bool) -> Vec<Option<GridCell>> {
+    let mut occupied: Vec<Vec<bool>> = Vec::new();
+    let mut cursor: usize = 0;
+    entities
+        .iter()
+        .map(|item| {
+            if item.control == LayoutControl::LinebreakMarker {
+                cursor = cursor.next_multiple_of(columns);
+                return None;
+            }
+            let (cols, rows) = if spans {
+                (item.span.cols.clamp(1, columns), item.span.rows.max(1))
+            } else {
+                (1, 1)
+            };
+            let fits = |occupied: &Vec<Vec<bool>>, x: usize, y: usize| {
+                x + cols <= columns
+                    && (y..y + rows).all(|y| {
+                        occupied
+                            .get(y)
+                            .is_none_or(|row| row[x..x + cols].iter().all(|c| !c))
+                    })
+            };
+            let mut index = cursor;
+            while !fits(&occupied, index % columns, index / columns) {
+                index += 1;
+            }
+            let (x, y) = (index % columns, index / columns);
+            if occupied.len() < y + rows {
+                occupied.resize(y + rows, vec![false; columns]);
+            }
+            for row in &mut occupied[y..y + rows] {
+                row[x..x + cols].fill(true);
+            }
+            cursor = index + cols;
+            if item.control == LayoutControl::Linebreak {
+                cursor = cursor.next_multiple_of(columns);
+            }
+            Some(GridCell { x, y, cols, rows })
+        })
+        .collect()
+}
+
+/// A grid of cells, placed left to right, top to bottom.
+///
+/// Each column is as wide as its widest item and each row is as tall as its tallest item,
+/// items with a [`GridSpan`](super::GridSpan) take multiple cells and grow the columns and rows
+/// they span evenly if they do not fit.
+/// [`LayoutControl::Linebreak`] ends a row after an item and
+/// [`LayoutControl::LinebreakMarker`] ends a row without taking a cell.
+/// [`LayoutRange`] operates on rows, an item is displayed if its top row is in range.
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct GridLayout {
+    /// Number of columns, `0` is treated as `1`.
+    pub columns: usize,
+}
+
+impl GridLayout {
+    pub const fn new(columns: usize) -> Self {
+        GridLayout { columns }
+    }
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Layout for GridLayout {
+    fn place(
+        &self,
+        info: &LayoutInfo,
+        entities: Vec<LayoutItem>,
+        range: &mut LayoutRange,
+    ) -> LayoutOutput {
+        let columns = self.columns.max(1);
+        let cells = grid_cells(&entities, columns, true);
+        let rows = cells
+            .iter()
+            .flatten()
+            .map(|c| c.y + c.rows)
+            .max()
+            .unwrap_or(0);
+        range.resolve(rows);
+        let visible = range.to_range(rows);
+        let items: Vec<_> = entities
+            .iter()
+            .zip(cells)
+            .filter_map(|(item, cell)| Some((item, cell?)))
+            .filter(|(_, cell)| visible.contains(&cell.y))
+            .map(|(item, cell)| {
+                let span = Vec2::new(cell.cols as f32, cell.rows as f32);
+                let pos = Vec2::new(cell.x as f32, (cell.y - visible.start) as f32);
+                (item, pos, span)
+            })
+            .collect();
+        if items.is_empty() {
+            return LayoutOutput {
+                max_count: rows,
+                ..Default::default()
+            };
+        }
+        let counts = items
+            .iter()
+            .fold(Vec2::ZERO, |a, (_, pos, span)| a.max(*pos + *span));
+        let mut widths = vec![0.0f32; counts.x as usize];
+        let mut heights = vec![0.0f32; counts.y as usize];
+        // Items spanning a single cell first, so spanning items only grow tracks if needed.
+        for spanning in [false, true] {
+            for (item, pos, span) in items.iter() {
+                if (span.x > 1.0) == spanning {
+                    let cols = pos.x as usize..(pos.x + span.x) as usize;
+                    fit_tracks(&mut widths[cols], item.dimension.x, info.margin.x);
+                }
+                if (span.y > 1.0) == spanning {
+                    let rows = pos.y as usize..(pos.y + span.y) as usize;
+                    fit_tracks(&mut heights[rows], item.dimension.y, info.margin.y);
+                }
+            }
+        }
+        let dimension = Vec2::new(
+            widths.iter().sum::<f32>() + info.margin.x * (counts.x - 1.0),
+            heights.iter().sum::<f32>() + info.margin.y * (counts.y - 1.0),
+        );
+        // Distance from the left and the top of the grid to the start of each track.
+        let starts = |tracks: &[f32], margin: f32| {
+            tracks
+                .iter()
+                .scan(0.0, |cursor, len| {
+                    let start = *cursor;
+                    *cursor += len + margin;
+                    Some(start)
+                })
+                .collect::<Vec<_>>()
+        };
+        let x = starts(&widths, info.margin.x);
+        let y = starts(&heights, info.margin.y);
+        let entity_anchors = items
+            .iter()
+            .map(|(item, pos, span)| {
+                let (c, r) = (pos.x as usize, pos.y as usize);
+                let (cols, rows) = (span.x as usize, span.y as usize);
+                let size = Vec2::new(
+                    widths[c..c + cols].iter().sum::<f32>() + info.margin.x * (span.x - 1.0),
+                    heights[r..r + rows].iter().sum::<f32>() + info.margin.y * (span.y - 1.0),
+                );
+                let min = Vec2::new(x[c], dimension.y - y[r] - size.y);
+                (item.entity, min + size * (item.anchor + 0.5))
+            })
+            .collect();
+        LayoutOutput {
+            entity_anchors,
+            dimension,
+            max_count: rows,
+            column_widths: widths,
+            ..Default::default()
+        }
+        .normalized()
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+
+    fn is_size_agnostic(&self) -> bool {
+        true
+    }
+}
+
+/// Grow `tracks` evenly so they fit `size`, including the margins between them.
+fn fit_tracks(tracks: &mut [f32], size: f32, margin: f32) {
+    let current = tracks.iter().sum::<f32>() + margin * tracks.len().saturating_sub(1) as f32;
+    if size > current {
+        let grow = (size - current) / tracks.len() as f32;
+        tracks.iter_mut().for_each(|len| *len += grow);
+    }
+}
+
+/// A table where each column is as wide as its widest item
+/// and each row is as tall as its tallest item,
+/// placed left to right, top to bottom.
+///
+/// [`LayoutControl::Linebreak`] ends a row after an item and
+/// [`LayoutControl::LinebreakMarker`] ends a row without taking a cell.
+/// [`LayoutRange`] operates on rows.
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct TableLayout {
+    /// Number of columns, `0` is treated as `1`.
+    pub columns: usize,
+}
+
+impl TableLayout {
+    pub const fn new(columns: usize) -> Self {
+        TableLayout { columns }
+    }
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Layout for TableLayout {
+    fn place(
+        &self,
+        info: &LayoutInfo,
+        entities: Vec<LayoutItem>,
+        range: &mut LayoutRange,
+    ) -> LayoutOutput {
+        let columns = self.columns.max(1);
+        let cells = grid_cells(&entities, columns, false);
+        let rows = cells.iter().flatten().map(|c| c.y + 1).max().unwrap_or(0);
+        range.resolve(rows);
+        let visible = range.to_range(rows);
+        let items: Vec<_> = entities
+            .iter()
+            .zip(cells)
+            .filter_map(|(item, cell)| Some((item, cell?)))
+            .filter(|(_, cell)| visible.contains(&cell.y))
+            .map(|(item, cell)| (item, cell.x, cell.y - visible.start))
+            .collect();
+        if items.is_empty() {
+            return LayoutOutput {
+                max_count: rows,
+                ..Default::default()
+            };
+        }
+        let mut widths = vec![0.0f32; items.iter().map(|(_, c, _)| c + 1).max().unwrap_or(0)];
+        let mut heights = vec![0.0f32; items.iter().map(|(.., r)| r + 1).max().unwrap_or(0)];
+        for (item, c, r) in items.iter().copied() {
+            widths[c] = widths[c].max(item.dimension.x);
+            heights[r] = heights[r].max(item.dimension.y);
+        }
+        let dimension = Vec2::new(
+            widths.iter().sum::<f32>() + info.margin.x * (widths.len() - 1) as f32,
+            heights.iter().sum::<f32>() + info.margin.y * (heights.len() - 1) as f32,
+        );
+        let mut x = Vec::with_capacity(widths.len());
+        let mut cursor = 0.0;
+        for w in &widths {
+            x.push(cursor);
+            cursor += w + info.margin.x;
+        }
+        let mut y = Vec::with_capacity(heights.len());
+        let mut cursor = dimension.y;
+        for h in &heights {
+            cursor -= h;
+            y.push(cursor);
+            cursor -= info.margin.y;
+        }
+        let entity_anchors = items
+            .iter()
+            .map(|(item, c, r)| {
+                let (c, r) = (*c, *r);
+                let min = Vec2::new(x[c], y[r]);
+                let cell = Vec2::new(widths[c], heights[r]);
+                (item.entity, min + cell * (item.anchor + 0.5))
+            })
+            .collect();
+        LayoutOutput {
+            entity_anchors,
+            dimension,
+            max_count: rows,
+            column_widths: widths,
+            ..Default::default()
+        }
+        .normalized()
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+
+    fn is_size_agnostic(&self) -> bool {
+        true
+    }
+}
+
+/// Places items evenly around a circle, useful for radial menus.
+///
+/// The `i`-th of `n` items is centered on `center + radius * (cos(a), sin(a))`,
+/// where angles are in radians, counter-clockwise from `+X` and
+///
+/// * `a = start_angle + sweep * i / n` if `sweep` is a full circle, since the last item
+///   would otherwise overlap the first one, or
+/// * `a = start_angle + sweep * i / (n - 1)` otherwise, placing items on both ends of the arc.
+///
+/// Dimension of the layout is `2 * radius` plus the dimension of the largest item.
+///
+/// This does not modify the rotation of items.
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
+pub struct RadialLayout {
+    /// Radius of the circle.
+    pub radius: f32,
+    /// Angle of the first item.
+    pub start_angle: f32,
+    /// Total angle spanned by items, default is `TAU`.
+    pub sweep: f32,
+}
+
+impl RadialLayout {
+    pub const fn new(radius: f32) -> Self {
+        RadialLayout {
+            radius,
+            start_angle: 0.0,
+            sweep: TAU,
+        }
+    }
+
+    /// Set angle of the first item.
+    pub const fn with_start_angle(mut self, angle: f32) -> Self {
+        self.start_angle = angle;
+        self
+    }
+
+    /// Set total angle spanned by items.
+    pub const fn with_sweep(mut self, sweep: f32) -> Self {
+        self.sweep = sweep;
+        self
+    }
+}
+
+impl Default for RadialLayout {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl Layout for RadialLayout {
+    fn place(
+        &self,
+        _: &LayoutInfo,
+        entities: Vec<LayoutItem>,
+        range: &mut LayoutRange,
+    ) -> LayoutOutput {
+        range.resolve(entities.len());
+        let items = &entities[range.to_range(entities.len())];
+        let max_dim = items.iter().fold(Vec2::ZERO, |a, b| a.max(b.dimension));
+        let dimension = Vec2::splat(self.radius * 2.0) + max_dim;
+        let gaps = if self.sweep.abs() >= TAU {
+            items.len()
+        } else {
+            items.len().saturating_sub(1)
+        }
+        .max(1);
+        let step = self.sweep / gaps as f32;
+        let entity_anchors = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let angle = self.start_angle + step * i as f32;
+                let center = dimension / 2.0 + Vec2::from_angle(angle) * self.radius;
+                (item.entity, center + item.anchor * item.dimension)
+            })
+            .collect();
+        LayoutOutput {
+            entity_anchors,
+            dimension,
+            max_count: entities.len(),
+            ..Default::default()
+        }
+        .normalized()
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+
+    fn is_size_agnostic(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::layout::GridSpan;
+
+    fn items(dimensions: &[Vec2]) -> Vec<LayoutItem> {
+        dimensions
+            .iter()
+            .enumerate()
+            .map(|(i, dimension)| {
+                LayoutItem::new(Entity::from_raw(i as u32), Vec2::ZERO, *dimension)
+            })
+            .collect()
+    }
+
+    fn place(layout: &dyn Layout, entities: Vec<LayoutItem>) -> LayoutOutput {
+        let info = LayoutInfo {
+            dimension: Vec2::ZERO,
+            margin: Vec2::ONE,
+        };
+        layout.place(&info, entities, &mut LayoutRange::All)
+    }
+
+    #[test]
+    fn grid_tracks_fit_their_items() {
+        let entities = items(&[
+            Vec2::new(10.0, 4.0),
+            Vec2::new(2.0, 6.0),
+            Vec2::new(4.0, 3.0),
+            Vec2::new(6.0, 2.0),
+        ]);
+        let output = place(&GridLayout::new(2), entities.clone());
+        assert_eq!(output.column_widths, vec![10.0, 6.0]);
+        assert_eq!(output.dimension, Vec2::new(17.0, 10.0));
+        // Centered in the bottom right cell, 6 wide and 3 tall.
+        assert_eq!(
+            output.entity_anchors[3].1,
+            Vec2::new(14.0, 1.5) / Vec2::new(17.0, 10.0) - 0.5
+        );
+
+        // A spanning item grows the columns it spans evenly.
+        let mut spanning = items(&[Vec2::new(20.0, 1.0)]);
+        spanning[0].entity = Entity::from_raw(4);
+        spanning[0].span = GridSpan::new(2, 1);
+        let output = place(&GridLayout::new(2), [entities, spanning].concat());
+        assert_eq!(output.column_widths, vec![11.5, 7.5]);
+        assert_eq!(output.dimension, Vec2::new(20.0, 12.0));
+    }
+
+    #[test]
+    fn empty_grid() {
+        let output = place(&GridLayout::new(3), Vec::new());
+        assert_eq!(output.dimension, Vec2::ZERO);
+        assert_eq!(output.max_count, 0);
+    }
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(actual.abs_diff_eq(expected, 1e-5), "{actual} != {expected}");
+    }
+
+    #[test]
+    fn radial_full_circle() {
+        let output = place(&RadialLayout::new(20.0), items(&[Vec2::splat(10.0); 4]));
+        assert_eq!(output.dimension, Vec2::splat(50.0));
+        // Gaps of a quarter turn, the last gap wraps around to the first item.
+        let expected = [Vec2::X, Vec2::Y, Vec2::NEG_X, Vec2::NEG_Y];
+        for ((_, anchor), direction) in output.entity_anchors.iter().zip(expected) {
+            assert_near(*anchor, direction * 20.0 / 50.0);
+        }
+    }
+
+    #[test]
+    fn radial_arc_includes_both_ends() {
+        let layout = RadialLayout::new(20.0).with_sweep(PI);
+        let output = place(&layout, items(&[Vec2::splat(10.0); 3]));
+        let expected = [Vec2::X, Vec2::Y, Vec2::NEG_X];
+        for ((_, anchor), direction) in output.entity_anchors.iter().zip(expected) {
+            assert_near(*anchor, direction * 20.0 / 50.0);
+        }
+    }
+
+    fn cells(spans: &[(usize, usize)], columns: usize) -> Vec<(usize, usize)> {
+        let mut entities = items(&vec![Vec2::ONE; spans.len()]);
+        for (item, (cols, rows)) in entities.iter_mut().zip(spans) {
+            item.span = GridSpan::new(*cols, *rows);
+        }
+        grid_cells(&entities, columns, true)
+            .into_iter()
+            .map(|cell| cell.map(|cell| (cell.x, cell.y)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn grid_spans_skip_occupied_cells() {
+        // A 2x1 item, following items fill the rest of the row.
+        assert_eq!(
+            cells(&[(2, 1), (1, 1), (1, 1), (2, 1), (1, 1)], 3),
+            vec![(0, 0), (2, 0), (0, 1), (1, 1), (0, 2)]
+        );
+        // A span that does not fit in the remaining columns wraps to the next row.
+        assert_eq!(
+            cells(&[(1, 1), (1, 1), (2, 1), (1, 1)], 3),
+            vec![(0, 0), (1, 0), (0, 1), (2, 1)]
+        );
+        // Cells taken by a 1x2 item are skipped in the next row, spans are capped by columns.
+        assert_eq!(
+            cells(&[(1, 2), (1, 1), (1, 1), (1, 1), (5, 1)], 3),
+            vec![(0, 0), (1, 0), (2, 0), (1, 1), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn forced_breaks_end_rows() {
+        let mut entities = items(&[
+            Vec2::new(4.0, 2.0),
+            Vec2::new(6.0, 2.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(3.0, 2.0),
+            Vec2::ZERO,
+            Vec2::new(1.0, 2.0),
+        ]);
+        entities[1].control = LayoutControl::Linebreak;
+        entities[5].control = LayoutControl::LinebreakMarker;
+        let cells: Vec<_> = grid_cells(&entities, 3, true)
+            .into_iter()
+            .map(|cell| cell.map(|cell| (cell.x, cell.y)))
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                Some((0, 0)),
+                Some((1, 0)),
+                Some((0, 1)),
+                Some((1, 1)),
+                Some((2, 1)),
+                None,
+                Some((0, 2)),
+            ]
+        );
+        // The last column is only taken in the middle row, short rows are aligned to the left.
+        let layouts: [&dyn Layout; 2] = [&GridLayout::new(3), &TableLayout::new(3)];
+        for layout in layouts {
+            let output = place(layout, entities.clone());
+            assert_eq!(output.column_widths, vec![4.0, 6.0, 3.0]);
+            assert_eq!(output.dimension, Vec2::new(15.0, 8.0));
+            assert_eq!(output.max_count, 3);
+            let anchor = |i: u32| {
+                output
+                    .entity_anchors
+                    .iter()
+                    .find(|(entity, _)| *entity == Entity::from_raw(i))
+                    .unwrap()
+                    .1
+            };
+            let center = |x: f32, y: f32| Vec2::new(x, y) / output.dimension - 0.5;
+            assert_eq!(output.entity_anchors.len(), 6);
+            assert_eq!(anchor(1), center(8.0, 7.0));
+            assert_eq!(anchor(4), center(13.5, 4.0));
+            assert_eq!(anchor(6), center(2.0, 1.0));
+        }
     }
 }
@@ -2,9 +2,12 @@
 
 pub(crate) mod container;
 pub(crate) mod layouts;
+pub(crate) mod registry;
 pub(crate) mod span;
 pub(crate) mod util;
 
+pub use crate::pipeline::measure_subtree;
 pub use container::*;
 pub use layouts::*;
+pub use registry::*;
 pub use util::*;
@@ -0,0 +1,205 @@
+use std::any::TypeId;
+use std::fmt::Debug;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+use bevy::ecs::system::Resource;
+use bevy::utils::HashMap;
+use serde::de::{DeserializeOwned, DeserializeSeed, Error as _, MapAccess, Visitor};
+use serde::ser::{Error as _, SerializeMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::*;
+
+static GLOBAL_REGISTRY: LazyLock<LayoutRegistry> = LazyLock::new(LayoutRegistry::with_builtins);
+
+#[derive(Clone)]
+struct LayoutEntry {
+    name: Arc<str>,
+    serialize: for<'t> fn(&'t dyn Layout) -> &'t dyn erased_serde::Serialize,
+    deserialize: for<'de> fn(
+        &mut dyn erased_serde::Deserializer<'de>,
+    ) -> Result<Box<dyn Layout>, erased_serde::Error>,
+}
+
+#[derive(Default)]
+struct LayoutRegistryInner {
+    by_type: HashMap<TypeId, LayoutEntry>,
+    by_name: HashMap<Arc<str>, LayoutEntry>,
+}
+
+/// Registry of [`Layout`]s that can be serialized as part of a [`LayoutObject`].
+///
+/// A [`LayoutObject`] is serialized as a map with a single entry,
+/// the registered name of the layout and its parameters, i.e. `{"StackLayout<X>": (...)}`.
+///
+/// All instances of this resource share the same global registry,
+/// since serde implementations cannot access the `World`.
+/// Built-in layouts are registered by default,
+/// layouts with a direction are registered for every valid direction, e.g. `SpanLayout<Stretch<Rev<Y>>>`.
+#[derive(Clone, Resource)]
+pub struct LayoutRegistry(Arc<RwLock<LayoutRegistryInner>>);
+
+impl Default for LayoutRegistry {
+    fn default() -> Self {
+        GLOBAL_REGISTRY.clone()
+    }
+}
+
+impl Debug for LayoutRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.0.read().unwrap_or_else(PoisonError::into_inner);
+        f.debug_set().entries(inner.by_name.keys()).finish()
+    }
+}
+
+macro_rules! register_all {
+    ($registry: expr, $($ty: ty),* $(,)?) => {
+        $($registry.register::<$ty>(stringify!($ty).replace(' ', ""));)*
+    };
+}
+
+impl LayoutRegistry {
+    fn with_builtins() -> Self {
+        let registry = LayoutRegistry(Default::default());
+        register_all!(
+            registry,
+            AbsoluteLayout,
+            BoundsLayout,
+            GridLayout,
+            TableLayout,
+            RadialLayout,
+            StackLayout<X>,
+            StackLayout<Rev<X>>,
+            StackLayout<Y>,
+            StackLayout<Rev<Y>>,
+            CompactLayout<X>,
+            CompactLayout<Rev<X>>,
+            CompactLayout<Y>,
+            CompactLayout<Rev<Y>>,
+            FlexLayout<X>,
+            FlexLayout<Rev<X>>,
+            FlexLayout<Y>,
+            FlexLayout<Rev<Y>>,
+            SpanLayout<X>,
+            SpanLayout<Rev<X>>,
+            SpanLayout<Y>,
+            SpanLayout<Rev<Y>>,
+            SpanLayout<Stretch<X>>,
+            SpanLayout<Stretch<Rev<X>>>,
+            SpanLayout<Stretch<Y>>,
+            SpanLayout<Stretch<Rev<Y>>>,
+            WrapLayout<X, Y>,
+            WrapLayout<Rev<X>, Y>,
+            WrapLayout<X, Rev<Y>>,
+            WrapLayout<Rev<X>, Rev<Y>>,
+            ParagraphLayout<X, Y>,
+            ParagraphLayout<Rev<X>, Y>,
+            ParagraphLayout<X, Rev<Y>>,
+            ParagraphLayout<Rev<X>, Rev<Y>>,
+            ParagraphLayout<Stretch<X>, Y>,
+            ParagraphLayout<Stretch<Rev<X>>, Y>,
+            ParagraphLayout<Stretch<X>, Rev<Y>>,
+            ParagraphLayout<Stretch<Rev<X>>, Rev<Y>>,
+        );
+        registry
+    }
+
+    /// Obtain the global registry.
+    pub fn global() -> Self {
+        GLOBAL_REGISTRY.clone()
+    }
+
+    /// Register a layout with a unique name, replaces the previous registration of the same type or name.
+    pub fn register<T: Layout + Serialize + DeserializeOwned>(&self, name: impl Into<Arc<str>>) {
+        let entry = LayoutEntry {
+            name: name.into(),
+            serialize: |layout| {
+                layout
+                    .as_any()
+                    .downcast_ref::<T>()
+                    .expect("Layout registered with the wrong type.")
+            },
+            deserialize: |deserializer| {
+                erased_serde::deserialize::<T>(deserializer).map(|x| Box::new(x) as Box<dyn Layout>)
+            },
+        };
+        let mut inner = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(previous) = inner.by_type.insert(TypeId::of::<T>(), entry.clone()) {
+            inner.by_name.remove(&previous.name);
+        }
+        inner.by_name.insert(entry.name.clone(), entry);
+    }
+
+    /// Returns the registered name of a layout.
+    pub fn name_of(&self, layout: &dyn Layout) -> Option<Arc<str>> {
+        let inner = self.0.read().unwrap_or_else(PoisonError::into_inner);
+        inner
+            .by_type
+            .get(&layout.as_any().type_id())
+            .map(|entry| entry.name.clone())
+    }
+
+    fn get_by_type(&self, layout: &dyn Layout) -> Option<LayoutEntry> {
+        let inner = self.0.read().unwrap_or_else(PoisonError::into_inner);
+        inner.by_type.get(&layout.as_any().type_id()).cloned()
+    }
+
+    fn get_by_name(&self, name: &str) -> Option<LayoutEntry> {
+        let inner = self.0.read().unwrap_or_else(PoisonError::into_inner);
+        inner.by_name.get(name).cloned()
+    }
+}
+
+impl Serialize for LayoutObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(entry) = GLOBAL_REGISTRY.get_by_type(self.0.as_ref()) else {
+            return Err(S::Error::custom(format!(
+                "Layout {:?} is not registered in LayoutRegistry.",
+                self.0
+            )));
+        };
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(entry.name.as_ref(), (entry.serialize)(self.0.as_ref()))?;
+        map.end()
+    }
+}
+
+struct LayoutSeed(LayoutEntry);
+
+impl<'de> DeserializeSeed<'de> for LayoutSeed {
+    type Value = Box<dyn Layout>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0.deserialize)(&mut deserializer).map_err(D::Error::custom)
+    }
+}
+
+struct LayoutVisitor;
+
+impl<'de> Visitor<'de> for LayoutVisitor {
+    type Value = LayoutObject;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a map with the name of a layout as its only key")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let Some(name) = map.next_key::<String>()? else {
+            return Err(A::Error::custom("Expected a layout, found an empty map."));
+        };
+        let Some(entry) = GLOBAL_REGISTRY.get_by_name(&name) else {
+            return Err(A::Error::custom(format!(
+                "Layout {name} is not registered in LayoutRegistry."
+            )));
+        };
+        let layout = map.next_value_seed(LayoutSeed(entry))?;
+        Ok(LayoutObject(layout))
+    }
+}
+
+impl<'de> Deserialize<'de> for LayoutObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(LayoutVisitor)
+    }
+}
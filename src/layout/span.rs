@@ -4,7 +4,8 @@ use bevy::ecs::entity::Entity;
 use bevy::math::Vec2;
 
 use crate::layout::{
-    Layout, LayoutControl, LayoutOutput, ParagraphLayout, SpanLayout, StackLayout,
+    CompactLayout, FlexLayout, Layout, LayoutControl, LayoutOutput, ParagraphLayout, SpanLayout,
+    StackLayout, WrapLayout,
 };
 
 use super::{util::*, LayoutInfo, LayoutRange};
@@ -18,7 +19,70 @@ impl<D: Direction> Layout for StackLayout<D> {
     ) -> LayoutOutput {
         let margin = parent.margin;
         range.resolve(entities.len());
-        stack::<D>(margin, &entities[range.to_range(entities.len())])
+        let mut items = trim(&entities[range.to_range(entities.len())], |x| {
+            x.control == LayoutControl::WhiteSpace
+        })
+        .to_vec();
+        if self.reversed {
+            items.reverse();
+        }
+        let mut entity_dimensions = Vec::new();
+        if let Some(len) = self.fill {
+            let gaps = items.len().saturating_sub(1) as f32;
+            let spacing = (D::len(margin) + self.separator.unwrap_or(0.0)) * gaps;
+            let occupied = items.iter().map(|x| D::len(x.dimension)).sum::<f32>();
+            if occupied > 0.0 {
+                let scale = ((len - spacing) / occupied).max(0.0);
+                for item in items.iter_mut() {
+                    item.dimension =
+                        D::side(item.dimension) + D::main(item.dimension).abs() * scale;
+                    if !matches!(
+                        item.control,
+                        LayoutControl::WhiteSpace | LayoutControl::Spacer
+                    ) {
+                        entity_dimensions.push((item.entity, item.dimension));
+                    }
+                }
+            }
+        }
+        let mut output = stack::<D>(margin, &items, self.cross_align, self.separator);
+        output.entity_dimensions = if self.cross_align == Align::Stretch {
+            stretch::<D>(&items, D::side(output.dimension).element_sum())
+        } else {
+            entity_dimensions
+        };
+        output.normalized().with_max(entities.len())
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+
+    fn is_size_agnostic(&self) -> bool {
+        self.fill.is_none()
+    }
+}
+
+impl<D: Direction> Layout for CompactLayout<D> {
+    fn place(
+        &self,
+        parent: &LayoutInfo,
+        entities: Vec<LayoutItem>,
+        range: &mut LayoutRange,
+    ) -> LayoutOutput {
+        range.resolve(entities.len());
+        // Runs of whitespace collapse into their first item, the rest are discarded.
+        let is_space = |x: &LayoutItem| x.control == LayoutControl::WhiteSpace;
+        let items: Vec<_> = entities[range.to_range(entities.len())]
+            .iter()
+            .scan(false, |after_space, item| {
+                let collapsed = *after_space && is_space(item);
+                *after_space = is_space(item);
+                Some((!collapsed).then(|| item.clone()))
+            })
+            .flatten()
+            .collect();
+        stack::<D>(parent.margin, &items, Align::Inherit, None)
             .normalized()
             .with_max(entities.len())
     }
@@ -43,11 +107,90 @@ impl<D: StretchDir> Layout for SpanLayout<D> {
         let dimension = parent.dimension;
         range.resolve(entities.len());
         let len = entities.len();
-        let entity_anchors = span::<D>(dimension, margin, &mut entities[range.to_range(len)]);
+        let items = &mut entities[range.to_range(len)];
+        if self.reversed {
+            items.reverse();
+        }
+        // Spacers absorb all leftover space, so items are packed from the start.
+        let justify = if fill_spacers::<D>(items, D::len(dimension), D::len(margin)) {
+            Justify::Start
+        } else {
+            self.justify
+        };
+        let entity_anchors = span::<D>(dimension, margin, items, self.cross_align, justify);
+        let entity_dimensions = if self.cross_align == Align::Stretch {
+            stretch::<D>(items, D::side(dimension).element_sum())
+        } else {
+            Vec::new()
+        };
         LayoutOutput {
             entity_anchors,
             dimension,
             max_count: entities.len(),
+            entity_dimensions,
+            ..Default::default()
+        }
+        .normalized()
+        .with_max(entities.len())
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+}
+
+impl<D: Direction> Layout for FlexLayout<D> {
+    fn place(
+        &self,
+        parent: &LayoutInfo,
+        entities: Vec<LayoutItem>,
+        range: &mut LayoutRange,
+    ) -> LayoutOutput {
+        let margin = parent.margin;
+        range.resolve(entities.len());
+        let mut items = trim(&entities[range.to_range(entities.len())], |x| {
+            x.control == LayoutControl::WhiteSpace
+        })
+        .to_vec();
+        for item in items.iter_mut() {
+            if item.control == LayoutControl::Spacer {
+                item.dimension = Vec2::ZERO;
+                item.grow = spacer_weight(item);
+                item.shrink = 0.0;
+            }
+        }
+        let occupied = items.iter().map(|x| D::len(x.dimension)).sum::<f32>()
+            + D::len(margin) * items.len().saturating_sub(1) as f32;
+        let leftover = D::len(parent.dimension) - occupied;
+        let (weights, total) = if leftover > 0.0 {
+            let total = items.iter().map(|x| x.grow.max(0.0)).sum::<f32>();
+            (
+                items.iter().map(|x| x.grow.max(0.0)).collect::<Vec<_>>(),
+                total,
+            )
+        } else {
+            let total = items.iter().map(|x| x.shrink.max(0.0)).sum::<f32>();
+            (
+                items.iter().map(|x| x.shrink.max(0.0)).collect::<Vec<_>>(),
+                total,
+            )
+        };
+        let mut entity_dimensions = Vec::new();
+        if leftover != 0.0 && total > 0.0 {
+            for (item, weight) in items.iter_mut().zip(weights) {
+                if weight == 0.0 {
+                    continue;
+                }
+                let len = (D::len(item.dimension) + leftover * weight / total).max(0.0);
+                item.dimension = D::side(item.dimension) + D::main_vec(len).abs();
+                if item.control != LayoutControl::Spacer {
+                    entity_dimensions.push((item.entity, item.dimension));
+                }
+            }
+        }
+        LayoutOutput {
+            entity_dimensions,
+            ..stack::<D>(margin, &items, Align::Inherit, None)
         }
         .normalized()
         .with_max(entities.len())
@@ -70,7 +213,33 @@ where
     ) -> LayoutOutput {
         let margin = parent.margin;
         let dim = parent.dimension;
-        paragraph::<D1, D2>(dim, margin, entities).normalized()
+        paragraph::<D1, D2>(dim, margin, entities, self.bidi).normalized()
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+}
+
+impl<D1: Direction, D2: Direction> Layout for WrapLayout<D1, D2>
+where
+    (D1, D2): DirectionPair,
+{
+    fn place(
+        &self,
+        parent: &LayoutInfo,
+        entities: Vec<LayoutItem>,
+        range: &mut LayoutRange,
+    ) -> LayoutOutput {
+        let lines = wrap_lines::<D1>(parent.dimension, parent.margin, entities);
+        range.resolve(lines.len());
+        wrap::<D1, D2>(
+            parent.dimension,
+            parent.margin,
+            &lines[range.to_range(lines.len())],
+        )
+        .normalized()
+        .with_max(lines.len())
     }
 
     fn dyn_clone(&self) -> Box<dyn Layout> {
@@ -96,36 +265,101 @@ fn trim<T>(slice: &[T], mut f: impl FnMut(&T) -> bool) -> &[T] {
     &slice[min..max]
 }
 
-pub(crate) fn stack<D: Direction>(margin: Vec2, items: &[LayoutItem]) -> LayoutOutput {
+/// Weight of a [`LayoutControl::Spacer`], its flex grow factor or `1` if not specified.
+fn spacer_weight(item: &LayoutItem) -> f32 {
+    if item.grow > 0.0 {
+        item.grow
+    } else {
+        1.0
+    }
+}
+
+/// Size [`LayoutControl::Spacer`]s to fill the leftover space on the main axis,
+/// returns `false` if there are no spacers.
+fn fill_spacers<D: Direction>(items: &mut [LayoutItem], size: f32, margin: f32) -> bool {
+    let is_spacer = |x: &LayoutItem| x.control == LayoutControl::Spacer;
+    if !items.iter().any(is_spacer) {
+        return false;
+    }
+    let trimmed = trim(items, |x| x.control == LayoutControl::WhiteSpace);
+    let occupied = trimmed
+        .iter()
+        .filter(|x| !is_spacer(x))
+        .map(|x| D::len(x.dimension))
+        .sum::<f32>()
+        + margin * trimmed.len().saturating_sub(1) as f32;
+    let leftover = (size - occupied).max(0.0);
+    let total = items
+        .iter()
+        .filter(|x| is_spacer(x))
+        .map(spacer_weight)
+        .sum::<f32>();
+    for item in items.iter_mut().filter(|x| is_spacer(x)) {
+        item.dimension = D::main_vec(leftover * spacer_weight(item) / total).abs();
+    }
+    true
+}
+
+/// Items stretched to `height` on the cross axis.
+fn stretch<D: Direction>(items: &[LayoutItem], height: f32) -> Vec<(Entity, Vec2)> {
+    items
+        .iter()
+        .filter(|x| !matches!(x.control, LayoutControl::WhiteSpace | LayoutControl::Spacer))
+        .map(|x| (x.entity, D::main(x.dimension).abs() + D::side_vec(height)))
+        .collect()
+}
+
+pub(crate) fn stack<D: Direction>(
+    margin: Vec2,
+    items: &[LayoutItem],
+    align: Align,
+    separator: Option<f32>,
+) -> LayoutOutput {
     let mut result = Vec::new();
+    let mut dividers = Vec::new();
     let margin = D::main(margin);
     let mut cursor = -margin;
-    let line_height = D::side_vec(1.0);
     let mut max_len = Vec2::ZERO;
     let items = trim(items, |x| x.control == LayoutControl::WhiteSpace);
-    for item in items {
+    for (i, item) in items.iter().enumerate() {
+        if let Some(thickness) = separator.filter(|_| i > 0) {
+            let thickness = D::main_vec(thickness);
+            dividers.push(cursor + margin + thickness / 2.0);
+            cursor += margin + thickness;
+        }
         cursor += margin;
 
         let width = D::main(item.dimension);
-        let size = width + line_height;
         max_len = max_len.max(item.dimension);
 
-        let anchor = cursor + (size / 2.0) + item.anchor * size.abs();
-        result.push((item.entity, anchor));
+        if item.control != LayoutControl::Spacer {
+            let anchor = cursor + (width / 2.0) + item.anchor * width.abs();
+            result.push((item.entity, anchor));
+        }
         cursor += width;
     }
 
-    let height_mult = D::side(max_len) + D::main(Vec2::ONE).abs();
-    result.iter_mut().for_each(|(_, x)| *x *= height_mult);
+    let height = D::side(max_len).element_sum();
+    let placed = items.iter().filter(|x| x.control != LayoutControl::Spacer);
+    for (item, (_, x)) in placed.zip(result.iter_mut()) {
+        *x += align.cross::<D>(item, height);
+    }
+    let mut dividers: Vec<_> = dividers
+        .into_iter()
+        .map(|x| (x + D::side_vec(height / 2.0), height))
+        .collect();
 
     if cursor.cmplt(Vec2::ZERO).any() {
         let roll = cursor.min(Vec2::ZERO);
         result.iter_mut().for_each(|(_, x)| *x -= roll);
+        dividers.iter_mut().for_each(|(x, _)| *x -= roll);
     }
     LayoutOutput {
         entity_anchors: result,
-        dimension: cursor.abs() + height_mult,
+        dimension: cursor.abs() + D::side(max_len),
         max_count: items.len(),
+        dividers,
+        ..Default::default()
     }
 }
 
@@ -133,11 +367,13 @@ pub(crate) fn span<D: StretchDir>(
     size: Vec2,
     margin: Vec2,
     items: &mut [LayoutItem],
+    align: Align,
+    justify: Justify,
 ) -> Vec<(Entity, Vec2)> {
     let mut result = Vec::new();
 
     let major_dim = D::Pos::main(size);
-    let minor_dim = D::Pos::side(size);
+    let height = D::Pos::side(size).element_sum();
 
     let mut neg_len = 0usize;
     let mut mid_len = 0usize;
@@ -147,6 +383,34 @@ pub(crate) fn span<D: StretchDir>(
         items.reverse();
     }
 
+    if justify != Justify::Inherit {
+        // items are reversed so `Start` and `End` should be swapped.
+        let justify = match justify {
+            Justify::Start if D::reversed() => Justify::End,
+            Justify::End if D::reversed() => Justify::Start,
+            justify => justify,
+        };
+        let items = trim(items, |x| x.control == LayoutControl::WhiteSpace);
+        let occupied = items.iter().map(|x| D::Pos::len(x.dimension)).sum::<f32>()
+            + D::Pos::len(margin) * items.len().saturating_sub(1) as f32;
+        let (mut cursor, gap) = justify.distribute(
+            D::Pos::len(size) - occupied,
+            items.len(),
+            D::Pos::len(margin),
+        );
+        for item in items {
+            let main = D::Pos::main(item.dimension * (item.anchor + 0.5));
+            if item.control != LayoutControl::Spacer {
+                result.push((
+                    item.entity,
+                    D::Pos::main_vec(cursor) + main + align.cross::<D>(item, height),
+                ));
+            }
+            cursor += D::Pos::len(item.dimension) + gap;
+        }
+        return result;
+    }
+
     items.iter().for_each(|x| match D::bucket(x.anchor) {
         Trinary::Neg => neg_len += 1,
         Trinary::Mid => mid_len += 1,
@@ -176,20 +440,29 @@ pub(crate) fn span<D: StretchDir>(
     let mut pos_cursor = Vec2::ZERO;
 
     for item in neg {
-        let cell_size = D::Pos::main(item.dimension) + minor_dim;
-        result.push((item.entity, neg_cursor + cell_size * (item.anchor + 0.5)));
+        let main = D::Pos::main(item.dimension * (item.anchor + 0.5));
+        result.push((
+            item.entity,
+            neg_cursor + main + align.cross::<D>(item, height),
+        ));
         neg_cursor += D::Pos::main(item.dimension)
     }
 
     for item in mid {
-        let cell_size = D::Pos::main(item.dimension) + minor_dim;
-        result.push((item.entity, mid_cursor + cell_size * (item.anchor + 0.5)));
+        let main = D::Pos::main(item.dimension * (item.anchor + 0.5));
+        result.push((
+            item.entity,
+            mid_cursor + main + align.cross::<D>(item, height),
+        ));
         mid_cursor += D::Pos::main(item.dimension)
     }
 
     for item in pos {
-        let cell_size = D::Pos::main(item.dimension) + minor_dim;
-        result.push((item.entity, pos_cursor + cell_size * (item.anchor + 0.5)));
+        let main = D::Pos::main(item.dimension * (item.anchor + 0.5));
+        result.push((
+            item.entity,
+            pos_cursor + main + align.cross::<D>(item, height),
+        ));
         pos_cursor += D::Pos::main(item.dimension)
     }
 
@@ -241,10 +514,66 @@ pub(crate) fn span<D: StretchDir>(
     result
 }
 
+/// Maximum descent and ascent of items with a baseline on the cross axis.
+fn baseline_metrics<D: Direction>(items: &[LayoutItem]) -> Option<(f32, f32)> {
+    items
+        .iter()
+        .filter_map(|x| {
+            let baseline = x.baseline?;
+            Some((baseline, D::side(x.dimension).element_sum() - baseline))
+        })
+        .reduce(|(d1, a1), (d2, a2)| (d1.max(d2), a1.max(a2)))
+}
+
+/// Height of a line of a paragraph, accounting for baselines.
+fn line_height<D1: Direction, D2: Direction>(items: &[LayoutItem]) -> Vec2 {
+    let height = items
+        .iter()
+        .map(|x| D2::main(x.dimension).abs())
+        .fold(Vec2::ZERO, |a, b| a.max(b));
+    match baseline_metrics::<D1>(items) {
+        Some((descent, ascent)) => height.max(D2::main(Vec2::splat(descent + ascent)).abs()),
+        None => height,
+    }
+}
+
+/// Place a line of a paragraph, in the reverse direction if `bidi` determines the line is right to left.
+///
+/// Items with a baseline are aligned by their baselines instead of their anchors.
+fn paragraph_line<D: StretchDir>(
+    size: Vec2,
+    margin: Vec2,
+    items: &mut [LayoutItem],
+    bidi: Bidi,
+) -> Vec<(Entity, Vec2)> {
+    let mut result = if bidi.is_rtl(items) {
+        span::<Rev<D>>(size, margin, items, Align::Inherit, Justify::Inherit)
+    } else {
+        span::<D>(size, margin, items, Align::Inherit, Justify::Inherit)
+    };
+    let Some((descent, _)) = baseline_metrics::<D>(items) else {
+        return result;
+    };
+    for (entity, position) in result.iter_mut() {
+        let Some(item) = items.iter().find(|x| x.entity == *entity) else {
+            continue;
+        };
+        let Some(baseline) = item.baseline else {
+            continue;
+        };
+        let anchor = D::side(item.anchor).element_sum();
+        let dim = D::side(item.dimension).element_sum();
+        *position =
+            *position - D::side(*position) + D::side_vec(descent - baseline + (anchor + 0.5) * dim);
+    }
+    result
+}
+
 pub(crate) fn paragraph<D1: StretchDir, D2: Direction>(
     size: Vec2,
     margin: Vec2,
     items: impl IntoIterator<Item = LayoutItem>,
+    bidi: Bidi,
 ) -> LayoutOutput {
     let margin_flat = D1::len(margin);
     let total = D1::len(size);
@@ -263,12 +592,10 @@ pub(crate) fn paragraph<D1: StretchDir, D2: Direction>(
             || last_linebreak
         {
             last_linebreak = false;
-            let line_height = buffer
-                .iter()
-                .map(|x: &LayoutItem| D2::main(x.dimension).abs())
-                .fold(Vec2::ZERO, |a, b| a.max(b));
+            let line_height = line_height::<D1, D2>(&buffer);
             let line_size = D1::main(size) + line_height;
-            let mut span = span::<D1>(line_size, margin, &mut mem::take(&mut buffer));
+            let mut span =
+                paragraph_line::<D1>(line_size, margin, &mut mem::take(&mut buffer), bidi);
             let line_height = if item.control == LayoutControl::LinebreakMarker {
                 D2::main(line_height.max(item.dimension))
             } else {
@@ -293,12 +620,9 @@ pub(crate) fn paragraph<D1: StretchDir, D2: Direction>(
     }
 
     if !buffer.is_empty() {
-        let line_height = buffer
-            .iter()
-            .map(|x: &LayoutItem| D2::main(x.dimension).abs())
-            .fold(Vec2::ZERO, |a, b| a.max(b));
+        let line_height = line_height::<D1, D2>(&buffer);
         let line_size = D1::main(size) + line_height;
-        let mut span = span::<D1>(line_size, margin, &mut buffer);
+        let mut span = paragraph_line::<D1>(line_size, margin, &mut buffer, bidi);
         cursor += D2::main(line_height).min(Vec2::ZERO);
         span.iter_mut().for_each(|(_, x)| *x += cursor);
         cursor += D2::main(line_height).max(Vec2::ZERO);
@@ -318,5 +642,195 @@ pub(crate) fn paragraph<D1: StretchDir, D2: Direction>(
         entity_anchors: result,
         dimension: cursor.abs() + D1::main(size),
         max_count: lines,
+        ..Default::default()
+    }
+}
+
+/// Split items into lines and their minimum line height.
+pub(crate) fn wrap_lines<D1: Direction>(
+    size: Vec2,
+    margin: Vec2,
+    items: impl IntoIterator<Item = LayoutItem>,
+) -> Vec<(Vec<LayoutItem>, f32)> {
+    let total = D1::len(size);
+    let gap = D1::len(margin);
+    let mut lines = Vec::new();
+    let mut line = Vec::new();
+    let mut len = 0.0;
+    for item in items {
+        if item.control == LayoutControl::LinebreakMarker {
+            lines.push((mem::take(&mut line), D1::side(item.dimension).element_sum()));
+            len = 0.0;
+            continue;
+        }
+        let item_len = D1::len(item.dimension);
+        if !line.is_empty() && len + gap + item_len > total {
+            lines.push((mem::take(&mut line), 0.0));
+            len = 0.0;
+        }
+        len += if line.is_empty() {
+            item_len
+        } else {
+            gap + item_len
+        };
+        let linebreak = item.control == LayoutControl::Linebreak;
+        line.push(item);
+        if linebreak {
+            lines.push((mem::take(&mut line), 0.0));
+            len = 0.0;
+        }
+    }
+    if !line.is_empty() {
+        lines.push((line, 0.0));
+    }
+    lines
+}
+
+/// Place lines produced by [`wrap_lines`], without trimming whitespace.
+pub(crate) fn wrap<D1: Direction, D2: Direction>(
+    size: Vec2,
+    margin: Vec2,
+    lines: &[(Vec<LayoutItem>, f32)],
+) -> LayoutOutput {
+    let total = D1::len(size);
+    let gap = D1::len(margin);
+    let line_gap = D2::len(margin);
+    let heights: Vec<f32> = lines
+        .iter()
+        .map(|(line, min)| line.iter().fold(*min, |a, b| a.max(D2::len(b.dimension))))
+        .collect();
+    let height = heights.iter().sum::<f32>() + line_gap * heights.len().saturating_sub(1) as f32;
+    let mut result = Vec::new();
+    let mut cross = 0.0;
+    for ((line, _), line_height) in lines.iter().zip(heights) {
+        let line_min = if D2::reversed() {
+            height - cross - line_height
+        } else {
+            cross
+        };
+        let mut main = 0.0;
+        for item in line {
+            let width = D1::len(item.dimension);
+            let item_min = if D1::reversed() {
+                total - main - width
+            } else {
+                main
+            };
+            let anchor = item.anchor + 0.5;
+            result.push((
+                item.entity,
+                D1::Pos::main_vec(item_min + D1::Pos::len(anchor) * width)
+                    + D2::Pos::main_vec(line_min + D2::Pos::len(anchor) * line_height),
+            ));
+            main += width + gap;
+        }
+        cross += line_height + line_gap;
+    }
+    LayoutOutput {
+        entity_anchors: result,
+        dimension: D1::Pos::main_vec(total) + D2::Pos::main_vec(height),
+        max_count: lines.len(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::entity::Entity;
+    use bevy::math::Vec2;
+
+    use super::*;
+    use crate::layout::{Container, LayoutControl, LayoutRange};
+
+    fn item(index: u32, control: LayoutControl) -> LayoutItem {
+        LayoutItem {
+            control,
+            ..LayoutItem::new(Entity::from_raw(index), Vec2::ZERO, Vec2::new(10.0, 10.0))
+        }
+    }
+
+    #[test]
+    fn compact_collapses_whitespace() {
+        let ws = LayoutControl::WhiteSpace;
+        let none = LayoutControl::None;
+        let controls = [ws, ws, none, ws, ws, ws, none, ws, none, ws];
+        let items: Vec<_> = controls
+            .iter()
+            .enumerate()
+            .map(|(i, control)| item(i as u32, *control))
+            .collect();
+        let mut container = Container::new(CompactLayout::HCOMPACT);
+        let output = container.compute(Vec2::ZERO, items.clone());
+        let placed: Vec<_> = output
+            .entity_anchors
+            .iter()
+            .map(|(e, _)| e.index())
+            .collect();
+        // Leading and trailing whitespace is trimmed, interior runs keep their first item.
+        assert_eq!(placed, vec![2, 3, 6, 7, 8]);
+        assert_eq!(
+            output.dimension.x,
+            Container::new(StackLayout::HSTACK)
+                .compute(
+                    Vec2::ZERO,
+                    items[2..4].iter().chain(&items[6..9]).cloned().collect()
+                )
+                .dimension
+                .x
+        );
+        assert_eq!(container.maximum, controls.len());
+    }
+
+    fn sized(widths: &[f32]) -> Vec<LayoutItem> {
+        widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                LayoutItem::new(
+                    Entity::from_raw(i as u32),
+                    Vec2::ZERO,
+                    Vec2::new(*width, 10.0),
+                )
+            })
+            .collect()
+    }
+
+    fn assert_mirrored(forward: &LayoutOutput, reversed: &LayoutOutput) {
+        assert_eq!(forward.dimension, reversed.dimension);
+        for ((a, x), (b, y)) in forward
+            .entity_anchors
+            .iter()
+            .zip(reversed.entity_anchors.iter().rev())
+        {
+            assert_eq!(a, b);
+            assert!((x.x + y.x).abs() < 1e-5, "{x} is not mirrored by {y}");
+            assert_eq!(x.y, y.y);
+        }
+    }
+
+    #[test]
+    fn reversed_anchors_are_mirrored() {
+        let items = sized(&[10.0, 20.0, 5.0, 15.0]);
+        let compute = |layout: StackLayout, range: LayoutRange| {
+            Container::new(layout)
+                .with_margin(Vec2::new(3.0, 0.0))
+                .with_range(range)
+                .compute(Vec2::ZERO, items.clone())
+        };
+        let forward = compute(StackLayout::HSTACK, LayoutRange::All);
+        let reversed = compute(StackLayout::HSTACK.with_reversed(true), LayoutRange::All);
+        assert_mirrored(&forward, &reversed);
+
+        // Ranges select items in the original order.
+        let range = LayoutRange::Bounded { min: 1, len: 2 };
+        let forward = compute(StackLayout::HSTACK, range);
+        let reversed = compute(StackLayout::HSTACK.with_reversed(true), range);
+        let placed: Vec<_> = reversed
+            .entity_anchors
+            .iter()
+            .map(|(e, _)| e.index())
+            .collect();
+        assert_eq!(placed, vec![2, 1]);
+        assert_mirrored(&forward, &reversed);
     }
 }
@@ -4,8 +4,10 @@ use std::marker::PhantomData;
 use bevy::ecs::entity::Entity;
 use bevy::math::Vec2;
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 
-use crate::layout::LayoutControl;
+use crate::layout::{GridSpan, LayoutControl, TextDirection};
+use crate::Anchor;
 
 /// Direction of a layout.
 pub trait Direction: Sized + Debug + Send + Sync + 'static {
@@ -79,11 +81,7 @@ impl Direction for X {
     }
 
     fn bucket(anchor: Vec2) -> Trinary {
-        match anchor.x {
-            x if x < -0.16 => Trinary::Neg,
-            x if x > 0.16 => Trinary::Pos,
-            _ => Trinary::Mid,
-        }
+        Trinary::from_sign(Anchor::from(anchor).bucket().x)
     }
 }
 
@@ -127,11 +125,7 @@ impl Direction for Y {
     }
 
     fn bucket(anchor: Vec2) -> Trinary {
-        match anchor.y {
-            y if y < -0.16 => Trinary::Neg,
-            y if y > 0.16 => Trinary::Pos,
-            _ => Trinary::Mid,
-        }
+        Trinary::from_sign(Anchor::from(anchor).bucket().y)
     }
 }
 
@@ -263,24 +257,155 @@ impl DirectionPair for (Stretch<Rev<X>>, Y) {}
 impl DirectionPair for (Stretch<X>, Rev<Y>) {}
 impl DirectionPair for (Stretch<Rev<X>>, Rev<Y>) {}
 
+/// Alignment of items on the cross axis of a layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum Align {
+    /// Use the item's anchor.
+    #[default]
+    Inherit,
+    /// Align to the left or bottom edge.
+    Start,
+    /// Align to the center.
+    Center,
+    /// Align to the right or top edge.
+    End,
+    /// Stretch the item's dimension to fill the cross axis.
+    Stretch,
+}
+
+impl Align {
+    /// Position of an item's anchor on the cross axis of a line of `height`.
+    pub(crate) fn cross<D: Direction>(self, item: &LayoutItem, height: f32) -> Vec2 {
+        let anchor = D::side(item.anchor).element_sum();
+        let dim = D::side(item.dimension).element_sum();
+        D::side_vec(match self {
+            Align::Inherit | Align::Stretch => (anchor + 0.5) * height,
+            Align::Start => (anchor + 0.5) * dim,
+            Align::Center => height / 2.0 + anchor * dim,
+            Align::End => height - (0.5 - anchor) * dim,
+        })
+    }
+}
+
+/// Distribution of leftover space on the main axis of a layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum Justify {
+    /// Group items by their anchors to the start, center and end of the layout.
+    #[default]
+    Inherit,
+    /// Pack items to the start.
+    Start,
+    /// Pack items to the center.
+    Center,
+    /// Pack items to the end.
+    End,
+    /// Distribute leftover space between items,
+    /// same as `Start` if there is only one item.
+    SpaceBetween,
+    /// Distribute leftover space around items,
+    /// gaps at both ends are half the size of gaps between items.
+    SpaceAround,
+    /// Distribute leftover space evenly between items and both ends.
+    SpaceEvenly,
+}
+
+impl Justify {
+    /// Returns the offset of the first item and gap between items.
+    ///
+    /// Negative leftover space is not distributed in `Space*` modes.
+    pub(crate) fn distribute(self, leftover: f32, count: usize, margin: f32) -> (f32, f32) {
+        let n = count as f32;
+        let space = leftover.max(0.0);
+        match self {
+            Justify::Inherit | Justify::Start => (0.0, margin),
+            Justify::Center => (leftover / 2.0, margin),
+            Justify::End => (leftover, margin),
+            Justify::SpaceBetween if count <= 1 => (0.0, margin),
+            Justify::SpaceBetween => (0.0, margin + space / (n - 1.0)),
+            Justify::SpaceAround => (space / n / 2.0, margin + space / n),
+            Justify::SpaceEvenly => (space / (n + 1.0), margin + space / (n + 1.0)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 pub(crate) enum Binary {
     Lo,
     Hi,
 }
 
-/// Info for positioning an item in a [`Container`].
-#[doc(hidden)]
+/// Direction of items in each line of a [`ParagraphLayout`](super::ParagraphLayout), default is `Ltr`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum Bidi {
+    /// Place items in the direction of the layout.
+    #[default]
+    Ltr,
+    /// Place items in the reverse direction of the layout.
+    Rtl,
+    /// Determine the direction of each line by the [`TextDirection`] of its first
+    /// item that is not [`LayoutControl::WhiteSpace`].
+    Auto,
+}
+
+impl Bidi {
+    /// Returns true if a line should be placed in the reverse direction.
+    pub(crate) fn is_rtl(self, line: &[LayoutItem]) -> bool {
+        match self {
+            Bidi::Ltr => false,
+            Bidi::Rtl => true,
+            Bidi::Auto => line
+                .iter()
+                .find(|x| x.control != LayoutControl::WhiteSpace)
+                .is_some_and(|x| x.direction == TextDirection::Rtl),
+        }
+    }
+}
+
+/// Info for positioning an item in a [`Container`](super::Container).
 #[derive(Debug, Clone)]
 pub struct LayoutItem {
     /// entity of the item
     pub entity: Entity,
     /// anchor of this item
     pub anchor: Vec2,
+    /// Anchor on the item itself matched with `anchor` on the container,
+    /// used by [`AbsoluteLayout`](super::AbsoluteLayout).
+    pub own_anchor: Vec2,
+    /// Offset from `anchor`, used by [`AbsoluteLayout`](super::AbsoluteLayout).
+    pub offset: Vec2,
     /// dimension of this item
     pub dimension: Vec2,
     /// Force a linebreak on or after this item.
     pub control: LayoutControl,
+    /// Flex grow factor of this item.
+    pub grow: f32,
+    /// Flex shrink factor of this item.
+    pub shrink: f32,
+    /// Number of cells taken by this item in a grid.
+    pub span: GridSpan,
+    /// Text direction of this item.
+    pub direction: TextDirection,
+    /// Distance from the bottom of this item to its text baseline.
+    pub baseline: Option<f32>,
+}
+
+impl LayoutItem {
+    /// Create an item without special behaviors.
+    pub fn new(entity: Entity, anchor: Vec2, dimension: Vec2) -> Self {
+        LayoutItem {
+            entity,
+            anchor,
+            own_anchor: anchor,
+            offset: Vec2::ZERO,
+            dimension,
+            control: LayoutControl::None,
+            grow: 0.0,
+            shrink: 1.0,
+            span: GridSpan::ONE,
+            direction: TextDirection::Ltr,
+            baseline: None,
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -291,6 +416,17 @@ pub enum Trinary {
     Pos,
 }
 
+impl Trinary {
+    /// Convert the sign of a value from [`Anchor::bucket`].
+    fn from_sign(v: i32) -> Trinary {
+        match v {
+            ..0 => Trinary::Neg,
+            0 => Trinary::Mid,
+            _ => Trinary::Pos,
+        }
+    }
+}
+
 pub(super) fn posx(v: Vec2) -> Vec2 {
     Vec2::new(v.x, 0.0)
 }
@@ -306,3 +442,56 @@ pub(super) fn posy(v: Vec2) -> Vec2 {
 pub(super) fn negy(v: Vec2) -> Vec2 {
     Vec2::new(0.0, -v.y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distribute_fills_leftover() {
+        let (leftover, margin) = (12.0, 2.0);
+        for justify in [
+            Justify::Start,
+            Justify::Center,
+            Justify::End,
+            Justify::SpaceBetween,
+            Justify::SpaceAround,
+            Justify::SpaceEvenly,
+        ] {
+            for count in 1..5 {
+                let (start, gap) = justify.distribute(leftover, count, margin);
+                // Space added between items on top of the margin.
+                let inner = (gap - margin) * (count - 1) as f32;
+                let end = leftover - start - inner;
+                assert!(
+                    start >= 0.0 && inner >= 0.0 && end >= -1e-5,
+                    "{justify:?} {count}"
+                );
+                match justify {
+                    Justify::SpaceBetween if count > 1 => assert!(start == 0.0 && end.abs() < 1e-5),
+                    Justify::SpaceAround if count > 1 => {
+                        assert!((end - start).abs() < 1e-5);
+                        assert!((gap - margin - 2.0 * start).abs() < 1e-5);
+                    }
+                    Justify::SpaceEvenly => {
+                        assert!((end - start).abs() < 1e-5);
+                        assert!((gap - margin - start).abs() < 1e-5 || count == 1);
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn distribute_negative_leftover() {
+        for justify in [
+            Justify::SpaceBetween,
+            Justify::SpaceAround,
+            Justify::SpaceEvenly,
+        ] {
+            assert_eq!(justify.distribute(-10.0, 3, 2.0), (0.0, 2.0));
+        }
+        assert_eq!(Justify::End.distribute(-10.0, 3, 2.0), (-10.0, 2.0));
+    }
+}
@@ -80,7 +80,8 @@
 //! ```
 //!
 //! If you want the opposite behavior, you can update the size of a sprite from
-//! the outputted [`RotatedRect::dimension`].
+//! the outputted [`RotatedRect::dimension`], or with the `sprite` feature,
+//! add `RectraySpritePlugin` and `SyncSpriteSize` from the `sprite` module.
 //!
 //! # Containers
 //!
@@ -91,23 +92,63 @@
 
 use bevy::app::{App, Plugin, PostUpdate, PreUpdate};
 use bevy::ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet};
+use bevy::picking::PickSet;
 use bevy::transform::TransformSystem;
-use layout::{Container, LayoutControl};
+use layout::{
+    ClipState, Container, FlexGrow, FlexShrink, GridSpan, Insets, LayoutControl, LayoutObject,
+    LayoutRange, LayoutRegistry, LayoutSortKey, RectrayClip, RectrayCull, TextBaseline,
+    TextDirection,
+};
 
+mod billboard;
+#[cfg(feature = "debug")]
+pub mod debug;
+mod drag;
+mod focus;
 mod hierarchy;
 
 pub mod layout;
+mod mesh;
 mod picking;
 mod pipeline;
 mod rect;
+mod scroll;
+#[cfg(feature = "sprite")]
+pub mod sprite;
+#[cfg(feature = "text")]
+pub mod text;
 mod transform;
 
+pub use billboard::{rectray_billboard, Billboard, RectrayBillboardPlugin};
+pub use drag::{rectray_drag_reorder, RectrayDragPlugin, RectrayDraggable};
+pub use focus::{
+    rectray_focus_navigation, rectray_keyboard_navigation, FocusDirection, RectrayFocusChanged,
+    RectrayFocusPlugin, RectrayFocusSettings, RectrayFocusable, RectrayFocused, RectrayNavigate,
+};
 pub use hierarchy::*;
-use picking::rectray_picking_backend;
-pub use picking::RectrayPickable;
-pub use pipeline::compute_transform_2d;
+pub use mesh::{sync_mesh_dimension, RectrayMeshPlugin, SyncMeshDimension};
+use picking::{
+    build_picking_grid, forward_render_target_pointers, rectray_picking_backend,
+    trigger_pointer_enter_leave, update_cached_inverse,
+};
+pub use picking::{
+    CachedInverseTransform, PickShape, PickingDisabled, RectrayHitTest, RectrayPickData,
+    RectrayPickable, RectrayPickingGrid, RectrayPointerEnter, RectrayPointerLeave,
+    RectrayRenderTargetPointer,
+};
+pub use pipeline::{
+    compute_frame, compute_transform_2d, fit_frame_content, snap_to_pixels, sync_nine_patch,
+    RectrayLayoutChanged,
+};
 pub use rect::{Anchor, RotatedRect};
-pub use transform::{Dimension, Transform2D};
+pub use scroll::{
+    rectray_scroll, rectray_scroll_target, rectray_scrollbar, RectrayScroll, RectrayScrollPlugin,
+    RectrayScrollbar, ScrollAxis, ScrollTarget, PIXELS_PER_LINE,
+};
+pub use transform::{
+    AspectMode, AspectRatio, Dimension, DimensionConstraints, DimensionPercent, NinePatchFrame,
+    OffsetSpace, PixelSnap, RotationSnap, Transform2D, ZIndex,
+};
 /// [`Plugin`] for `bevy_rectray`.
 #[derive(Debug, Clone, Copy)]
 pub struct RectrayPlugin;
@@ -120,15 +161,61 @@ impl Plugin for RectrayPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Transform2D>();
         app.register_type::<Dimension>();
+        app.register_type::<DimensionConstraints>();
+        app.register_type::<AspectRatio>();
+        app.register_type::<DimensionPercent>();
+        app.register_type::<NinePatchFrame>();
+        app.register_type::<PixelSnap>();
+        app.register_type::<RotationSnap>();
+        app.register_type::<ZIndex>();
         app.register_type::<Container>();
+        app.register_type::<LayoutObject>();
+        app.register_type::<LayoutRange>();
+        app.register_type::<Insets>();
         app.register_type::<RotatedRect>();
         app.register_type::<LayoutControl>();
+        app.register_type::<FlexGrow>();
+        app.register_type::<FlexShrink>();
+        app.register_type::<GridSpan>();
+        app.register_type::<LayoutSortKey>();
+        app.register_type::<RectrayClip>();
+        app.register_type::<RectrayCull>();
+        app.register_type::<ClipState>();
+        app.register_type::<TextDirection>();
+        app.register_type::<TextBaseline>();
+        app.register_type::<RectrayPickData>();
+        app.register_type::<PickShape>();
+        app.register_type::<PickingDisabled>();
+        app.init_resource::<LayoutRegistry>();
+        app.add_event::<RectrayLayoutChanged>();
         app.configure_sets(
             PostUpdate,
             RectrayTransformSet.before(TransformSystem::TransformPropagate),
         );
-        app.add_systems(PreUpdate, rectray_picking_backend);
-        app.add_systems(PostUpdate, compute_transform_2d.in_set(RectrayTransformSet));
+        app.add_systems(
+            PreUpdate,
+            (
+                update_cached_inverse,
+                build_picking_grid,
+                rectray_picking_backend,
+            )
+                .chain(),
+        );
+        app.add_systems(
+            PreUpdate,
+            (trigger_pointer_enter_leave, forward_render_target_pointers)
+                .in_set(PickSet::PostFocus),
+        );
+        app.add_systems(
+            PostUpdate,
+            (
+                compute_transform_2d,
+                (fit_frame_content, sync_nine_patch),
+                snap_to_pixels,
+            )
+                .chain()
+                .in_set(RectrayTransformSet),
+        );
     }
 }
 
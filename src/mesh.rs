@@ -0,0 +1,71 @@
+//! Keep meshes in sync with the size of their parent rect.
+//!
+//! Add [`RectrayMeshPlugin`] and add [`SyncMeshDimension`] to a `Mesh3d` child
+//! of an entity with a [`Dimension`](crate::Dimension).
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::{
+    component::Component, reflect::ReflectComponent, schedule::IntoSystemConfigs, system::Query,
+};
+use bevy::hierarchy::Parent;
+use bevy::math::Vec2;
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::transform::{components::Transform, TransformSystem};
+
+use crate::{RectrayTransformSet, RotatedRect};
+
+/// Scale a mesh to the [`RotatedRect::dimension`] of its parent.
+///
+/// `size` is the size of the mesh on the xy plane, e.g. `Vec2::ONE` for `Rectangle::new(1.0, 1.0)`
+/// or `Plane3d::new(Vec3::Z, Vec2::splat(0.5))`. The mesh is not regenerated,
+/// only the x and y scale of the entity's `Transform` are modified.
+///
+/// This should be added to a child with a plain `Transform`, not to the entity with the `Dimension`,
+/// since `Transform.scale` of that entity is controlled by [`Transform2D`](crate::Transform2D).
+#[derive(Debug, Clone, Copy, Component, Reflect, PartialEq)]
+#[reflect(Component, Default)]
+pub struct SyncMeshDimension {
+    /// Size of the mesh at scale `1`.
+    pub size: Vec2,
+}
+
+impl Default for SyncMeshDimension {
+    fn default() -> Self {
+        Self { size: Vec2::ONE }
+    }
+}
+
+/// [`Plugin`] for [`SyncMeshDimension`].
+#[derive(Debug, Clone, Copy)]
+pub struct RectrayMeshPlugin;
+
+impl Plugin for RectrayMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SyncMeshDimension>();
+        app.add_systems(
+            PostUpdate,
+            sync_mesh_dimension
+                .after(RectrayTransformSet)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Scale entities with [`SyncMeshDimension`] to their parent's [`RotatedRect`].
+pub fn sync_mesh_dimension(
+    mut query: Query<(&SyncMeshDimension, &mut Transform, &Parent)>,
+    parents: Query<&RotatedRect>,
+) {
+    for (sync, mut transform, parent) in query.iter_mut() {
+        let Ok(rect) = parents.get(parent.get()) else {
+            continue;
+        };
+        if sync.size.cmpeq(Vec2::ZERO).any() {
+            continue;
+        }
+        let scale = (rect.dimension / sync.size).extend(transform.scale.z);
+        if transform.scale != scale {
+            transform.scale = scale;
+        }
+    }
+}
@@ -13,46 +13,592 @@
 //! Add [`RectrayPickable`] and [`PickableBundle`](bevy_mod_picking::PickableBundle) to entities you want to be pickable, that's it!
 
 #![allow(clippy::type_complexity)]
+use bevy::ecs::reflect::ReflectComponent;
 use bevy::ecs::{
     component::Component,
-    entity::Entity,
-    event::EventWriter,
-    query::With,
-    system::{Query, Res},
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    event::{Event, EventReader, EventWriter},
+    query::{Added, Changed, Or, With, Without},
+    removal_detection::RemovedComponents,
+    system::{Commands, Local, Query, Res, ResMut, Resource, SystemParam},
 };
-use bevy::math::{primitives::InfinitePlane3d, Vec2, Vec3Swizzles};
-use bevy::transform::components::GlobalTransform;
+use bevy::hierarchy::{HierarchyQueryExt, Parent};
+use bevy::math::{primitives::InfinitePlane3d, Affine3A, IVec2, Ray3d, Vec2, Vec3, Vec3Swizzles};
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::transform::components::{GlobalTransform, Transform};
+use bevy::utils::HashMap;
 use bevy::{
-    picking::backend::{ray::RayMap, HitData, PointerHits},
+    picking::{
+        backend::{ray::RayMap, HitData, PointerHits},
+        events::{Down, Pointer, Up},
+        focus::PickingInteraction,
+        pointer::{
+            Location, PointerAction, PointerId, PointerInput, PointerLocation, PressDirection,
+        },
+    },
     prelude::Camera,
-    render::view::RenderLayers,
+    render::{camera::RenderTarget, view::RenderLayers},
 };
 
-use crate::{Dimension, RotatedRect, Transform2D};
+use crate::{
+    frame_local_to_world, ray_to_frame_local, Dimension, RectrayFrame, RotatedRect, Transform2D,
+};
 
 /// Make an item pickable in the `bevy_rectray` backend.
 ///
 /// Note: alternatives like the raycast backend or the sprite backend might be more desireable in some cases.
 #[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq)]
-#[require(Transform2D, Dimension)]
+#[require(Transform2D, Dimension, CachedInverseTransform)]
 pub struct RectrayPickable;
 
+/// Temporarily disable picking of a [`RectrayPickable`] without removing it,
+/// the entity is skipped by the backend and [`RectrayHitTest`].
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default)]
+pub struct PickingDisabled;
+
+/// Cached inverse of the entity's own `GlobalTransform`, required by [`RectrayPickable`],
+/// used to convert ray hits on the entity's plane to its local space.
+///
+/// Updated by `update_cached_inverse` when added or when `GlobalTransform` changes.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq)]
+pub struct CachedInverseTransform(pub Affine3A);
+
+/// Update [`CachedInverseTransform`] of entities with a changed `GlobalTransform`
+/// or a newly added [`CachedInverseTransform`].
+pub fn update_cached_inverse(
+    mut query: Query<
+        (&GlobalTransform, &mut CachedInverseTransform),
+        Or<(Changed<GlobalTransform>, Added<CachedInverseTransform>)>,
+    >,
+) {
+    query.iter_mut().for_each(|(global, mut inverse)| {
+        inverse.0 = global.affine().inverse();
+    });
+}
+
+/// Uniform grid of the bounding boxes of [`RectrayPickable`] rects, keyed by owning frame and cell.
+///
+/// Insert this resource to opt in, the picking backend then only tests rects in the cell
+/// containing the ray's hit point on each frame, instead of every pickable rect.
+/// Bounding boxes are in the layout space of the nearest ancestor [`RectrayFrame`],
+/// rects spanning multiple cells are added to each of them.
+/// Rects outside of a frame are always tested.
+///
+/// Rebuilt every frame by `build_picking_grid`, if empty, the backend falls back to a linear scan.
+#[derive(Debug, Clone, Resource)]
+pub struct RectrayPickingGrid {
+    /// Size of a cell in the frame's layout space.
+    pub cell_size: f32,
+    /// Cells of each frame and the frame's layout scale.
+    frames: EntityHashMap<(Vec2, HashMap<IVec2, Vec<Entity>>)>,
+    unparented: Vec<Entity>,
+}
+
+impl RectrayPickingGrid {
+    pub fn new(cell_size: f32) -> Self {
+        RectrayPickingGrid {
+            cell_size,
+            frames: EntityHashMap::default(),
+            unparented: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if no rects are in the grid.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty() && self.unparented.is_empty()
+    }
+
+    fn cell(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+
+    /// Find entities that might be hit by a ray.
+    fn candidates(&self, ray: Ray3d, frames: &Query<&GlobalTransform>, out: &mut Vec<Entity>) {
+        out.extend(self.unparented.iter().copied());
+        for (frame, (scale, cells)) in self.frames.iter() {
+            let Ok(global) = frames.get(*frame) else {
+                continue;
+            };
+            let Some(point) = ray_to_frame_local(global, ray) else {
+                continue;
+            };
+            if let Some(entities) = cells.get(&self.cell(point * *scale)) {
+                out.extend(entities.iter().copied());
+            }
+        }
+    }
+}
+
+impl Default for RectrayPickingGrid {
+    fn default() -> Self {
+        Self::new(64.0)
+    }
+}
+
+/// Rebuild [`RectrayPickingGrid`] if present.
+///
+/// Corners of each rect are projected onto the plane of its owning frame with `GlobalTransform`s,
+/// the same way rays are, then scaled to the frame's layout space.
+pub fn build_picking_grid(
+    grid: Option<ResMut<RectrayPickingGrid>>,
+    query: Query<
+        (Entity, &RotatedRect, &GlobalTransform),
+        (With<RectrayPickable>, Without<PickingDisabled>),
+    >,
+    frames: Query<(&GlobalTransform, &Transform, &RectrayFrame)>,
+    parents: Query<&Parent>,
+) {
+    let Some(mut grid) = grid else {
+        return;
+    };
+    let grid = grid.as_mut();
+    grid.frames.clear();
+    grid.unparented.clear();
+    if grid.cell_size.is_nan() || grid.cell_size <= 0.0 {
+        return;
+    }
+    for (entity, rect, global) in query.iter() {
+        let owner = parents
+            .iter_ancestors(entity)
+            .find_map(|parent| Some(parent).zip(frames.get(parent).ok()));
+        let Some((frame, (frame_global, frame_transform, frame_info))) = owner else {
+            grid.unparented.push(entity);
+            continue;
+        };
+        let scale = frame_info
+            .layout_scale(frame_transform)
+            .unwrap_or(Vec2::ONE);
+        // `GlobalTransform` is placed on the center of the rect.
+        let to_frame = frame_global.affine().inverse() * global.affine();
+        let half = rect.dimension / 2.0;
+        let corners = [
+            Vec2::new(-half.x, -half.y),
+            Vec2::new(half.x, -half.y),
+            Vec2::new(-half.x, half.y),
+            Vec2::new(half.x, half.y),
+        ]
+        .map(|corner| to_frame.transform_point3(corner.extend(0.0)).xy() * scale);
+        let min = corners.into_iter().reduce(Vec2::min).unwrap_or_default();
+        let max = corners.into_iter().reduce(Vec2::max).unwrap_or_default();
+        if !min.is_finite() || !max.is_finite() {
+            grid.unparented.push(entity);
+            continue;
+        }
+        let min = grid.cell(min);
+        let max = grid.cell(max);
+        let (_, cells) = grid
+            .frames
+            .entry(frame)
+            .or_insert_with(|| (scale, HashMap::default()));
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                cells.entry(IVec2::new(x, y)).or_default().push(entity);
+            }
+        }
+    }
+}
+
+/// Shape used for hit testing in the `bevy_rectray` picking backend, default is `Rect`.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub enum PickShape {
+    /// The entire rect.
+    #[default]
+    Rect,
+    /// Ellipse inscribed in the rect.
+    Ellipse,
+    /// Rect with rounded corners, `radius` is capped at half of the shorter side.
+    RoundedRect { radius: f32 },
+}
+
+impl PickShape {
+    /// Check if a point in the rect's own space, see [`RectrayPickData::local`], is inside the shape.
+    pub fn contains(&self, local: Vec2, dimension: Vec2) -> bool {
+        let half_dim = dimension / 2.0;
+        match *self {
+            PickShape::Rect => local.abs().cmple(half_dim).all(),
+            PickShape::Ellipse => (local / half_dim).length_squared() <= 1.0,
+            PickShape::RoundedRect { radius } => {
+                let radius = radius.clamp(0.0, half_dim.min_element());
+                let local = local.abs();
+                local.cmple(half_dim).all()
+                    && (local - (half_dim - radius)).max(Vec2::ZERO).length() <= radius
+            }
+        }
+    }
+}
+
+/// Position of the latest pointer hit on a [`RectrayPickable`] entity,
+/// updated by the `bevy_rectray` picking backend.
+///
+/// Add this component to opt in.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct RectrayPickData {
+    /// Normalized position in the rect, `[0, 0]` is the bottom left and `[1, 1]` is the top right.
+    pub uv: Vec2,
+    /// Position in the rect's own space, centered on the rect and unaffected by rotation and scale.
+    pub local: Vec2,
+}
+
+/// Triggered on a [`RectrayPickable`] entity when a pointer starts hovering it,
+/// based on changes of its `PickingInteraction`.
+///
+/// `data` is set if the entity has [`RectrayPickData`].
+#[derive(Debug, Clone, Copy, PartialEq, Event)]
+pub struct RectrayPointerEnter {
+    pub data: Option<RectrayPickData>,
+}
+
+/// Triggered on a [`RectrayPickable`] entity when no pointer is hovering it anymore,
+/// based on changes of its `PickingInteraction`.
+///
+/// `data` is set if the entity has [`RectrayPickData`], this is the position of the last hit.
+#[derive(Debug, Clone, Copy, PartialEq, Event)]
+pub struct RectrayPointerLeave {
+    pub data: Option<RectrayPickData>,
+}
+
+/// Trigger [`RectrayPointerEnter`] and [`RectrayPointerLeave`] on changes of `PickingInteraction`.
+pub fn trigger_pointer_enter_leave(
+    mut hovered: Local<EntityHashSet>,
+    mut commands: Commands,
+    query: Query<
+        (Entity, &PickingInteraction, Option<&RectrayPickData>),
+        (With<RectrayPickable>, Changed<PickingInteraction>),
+    >,
+    mut removed: RemovedComponents<PickingInteraction>,
+) {
+    for entity in removed.read() {
+        hovered.remove(&entity);
+    }
+    for (entity, interaction, data) in query.iter() {
+        let data = data.copied();
+        match interaction {
+            PickingInteraction::None => {
+                if hovered.remove(&entity) {
+                    commands.trigger_targets(RectrayPointerLeave { data }, entity);
+                }
+            }
+            PickingInteraction::Hovered | PickingInteraction::Pressed => {
+                if hovered.insert(entity) {
+                    commands.trigger_targets(RectrayPointerEnter { data }, entity);
+                }
+            }
+        }
+    }
+}
+
+/// Forward pointers hovering a [`RectrayPickable`] entity displaying a render target, i.e. an in-world screen,
+/// to a pointer on that render target, so entities rendered to it by another camera can be picked.
+///
+/// `pointer` should be a `PointerId::Custom` spawned by the user with a `PointerLocation`,
+/// its position is derived from [`RectrayPickData::uv`], with `uv` `[0, 0]`
+/// mapped to the bottom left of the render target. Movement and presses are sent as `PointerInput`s
+/// and take effect in the next frame. The location is cleared when no pointer hovers the entity.
+#[derive(Debug, Clone, Component)]
+#[require(RectrayPickable, RectrayPickData, PickingInteraction)]
+pub struct RectrayRenderTargetPointer {
+    /// Render target of a camera, usually `RenderTarget::Image`.
+    pub target: RenderTarget,
+    /// Id of the pointer on the image.
+    pub pointer: PointerId,
+    last: Option<Vec2>,
+}
+
+impl RectrayRenderTargetPointer {
+    pub fn new(target: RenderTarget, pointer: PointerId) -> Self {
+        RectrayRenderTargetPointer {
+            target,
+            pointer,
+            last: None,
+        }
+    }
+}
+
+/// Send `PointerInput`s for [`RectrayRenderTargetPointer`]s.
+pub fn forward_render_target_pointers(
+    mut query: Query<(
+        Entity,
+        &mut RectrayRenderTargetPointer,
+        &RectrayPickData,
+        &PickingInteraction,
+    )>,
+    cameras: Query<&Camera>,
+    mut pointers: Query<(&PointerId, &mut PointerLocation)>,
+    mut down: EventReader<Pointer<Down>>,
+    mut up: EventReader<Pointer<Up>>,
+    mut writer: EventWriter<PointerInput>,
+) {
+    let presses: Vec<_> = down
+        .read()
+        .map(|ev| (ev.target, ev.button, PressDirection::Down))
+        .chain(
+            up.read()
+                .map(|ev| (ev.target, ev.button, PressDirection::Up)),
+        )
+        .collect();
+    for (entity, mut forward, data, interaction) in query.iter_mut() {
+        if *interaction == PickingInteraction::None {
+            if forward.last.take().is_some() {
+                pointers
+                    .iter_mut()
+                    .filter(|(id, _)| **id == forward.pointer)
+                    .for_each(|(_, mut location)| location.location = None);
+            }
+            continue;
+        }
+        let Some(target) = forward.target.normalize(None) else {
+            continue;
+        };
+        let Some(size) = cameras
+            .iter()
+            .find(|camera| camera.target.normalize(None).as_ref() == Some(&target))
+            .and_then(|camera| camera.logical_target_size())
+        else {
+            continue;
+        };
+        // Viewport coordinates start from the top left.
+        let position = Vec2::new(data.uv.x, 1.0 - data.uv.y) * size;
+        let location = Location { target, position };
+        if forward.last != Some(position) {
+            let delta = position - forward.last.unwrap_or(position);
+            writer.send(PointerInput::new(
+                forward.pointer,
+                location.clone(),
+                PointerAction::Moved { delta },
+            ));
+            forward.last = Some(position);
+        }
+        for (_, button, direction) in presses.iter().filter(|(target, ..)| *target == entity) {
+            writer.send(PointerInput::new(
+                forward.pointer,
+                location.clone(),
+                PointerAction::Pressed {
+                    direction: *direction,
+                    button: *button,
+                },
+            ));
+        }
+    }
+}
+
+/// Intersection of a ray and the plane of a [`RotatedRect`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RectHit {
+    /// Distance from the origin of the ray.
+    pub depth: f32,
+    /// Position in world space.
+    pub position: Vec3,
+    /// Position in the entity's local space, centered on the rect and unaffected by rotation and scale.
+    pub local: Vec2,
+    /// Dimension of the rect.
+    pub dimension: Vec2,
+}
+
+impl RectHit {
+    pub fn is_inside(&self) -> bool {
+        PickShape::Rect.contains(self.local, self.dimension)
+    }
+
+    /// Position in the rect's own space, centered on the rect and unaffected by rotation and scale.
+    pub fn local(&self) -> Vec2 {
+        self.local
+    }
+}
+
+/// Intersect a ray with the plane of a [`RotatedRect`].
+pub(crate) fn ray_cast_rect(
+    ray: Ray3d,
+    global: &GlobalTransform,
+    rect: &RotatedRect,
+) -> Option<RectHit> {
+    ray_cast_rect_with_inverse(ray, global, &global.affine().inverse(), rect)
+}
+
+/// Intersect a ray with the plane of a [`RotatedRect`], `inverse` is the inverse of `global`.
+///
+/// The test is done in the entity's local space, since `RotatedRect` is not in the space
+/// of `Transform` under [`ScaleMode::LayoutSpace`](crate::ScaleMode::LayoutSpace).
+pub(crate) fn ray_cast_rect_with_inverse(
+    ray: Ray3d,
+    global: &GlobalTransform,
+    inverse: &Affine3A,
+    rect: &RotatedRect,
+) -> Option<RectHit> {
+    let plane = InfinitePlane3d::new(global.forward());
+    let depth = ray.intersect_plane(global.translation(), plane)?;
+    let position = ray.get_point(depth);
+    Some(RectHit {
+        depth,
+        position,
+        // `GlobalTransform` is placed on the center of the rect.
+        local: inverse.transform_point3(position).xy(),
+        dimension: rect.dimension,
+    })
+}
+
+/// Find [`RectrayPickable`]s at a point in the local space of a [`RectrayFrame`],
+/// without pointers or rays, e.g. for scripted tests.
+///
+/// Uses [`PickShape`]s and `GlobalTransform`s from the last transform propagation.
+#[derive(SystemParam)]
+pub struct RectrayHitTest<'w, 's> {
+    pickables: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            &'static RotatedRect,
+            Option<&'static PickShape>,
+        ),
+        (With<RectrayPickable>, Without<PickingDisabled>),
+    >,
+    frames: Query<'w, 's, (&'static GlobalTransform, &'static RectrayFrame)>,
+    parents: Query<'w, 's, &'static Parent>,
+}
+
+impl RectrayHitTest<'_, '_> {
+    /// Find the topmost pickable in `frame` at `point`.
+    pub fn hit(&self, frame: Entity, point: Vec2) -> Option<Entity> {
+        self.hit_all(frame, point).into_iter().next()
+    }
+
+    /// Find all pickables in `frame` at `point`, sorted from top to bottom by `z` in the frame.
+    ///
+    /// Pickables in nested frames belong to the nested frame.
+    pub fn hit_all(&self, frame: Entity, point: Vec2) -> Vec<Entity> {
+        let Ok((frame_global, frame_info)) = self.frames.get(frame) else {
+            return Vec::new();
+        };
+        let frame_inverse = frame_global.affine().inverse();
+        let world = frame_local_to_world(frame_global, frame_info, point);
+        let mut hits = Vec::new();
+        for (entity, global, rect, shape) in self.pickables.iter() {
+            let owner = self
+                .parents
+                .iter_ancestors(entity)
+                .find(|parent| self.frames.contains(*parent));
+            if owner != Some(frame) {
+                continue;
+            }
+            // `GlobalTransform` is placed on the center of the rect.
+            let local = global.affine().inverse().transform_point3(world).xy();
+            if shape
+                .copied()
+                .unwrap_or_default()
+                .contains(local, rect.dimension)
+            {
+                hits.push((
+                    entity,
+                    frame_inverse.transform_point3(global.translation()).z,
+                ));
+            }
+        }
+        hits.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        hits.into_iter().map(|(entity, _)| entity).collect()
+    }
+}
+
+/// Maximum difference in depth considered a tie.
+const DEPTH_TIE: f32 = 1e-4;
+
+/// Break ties in depth by [`RotatedRect::z`], so rects with higher `z` are picked first.
+///
+/// Depths that are not tied are not modified.
+fn bias_tied_depths(picks: &mut [(Entity, HitData)], z_values: &[f32]) {
+    let depths: Vec<f32> = picks.iter().map(|(_, hit)| hit.depth).collect();
+    for ((_, hit), z) in picks.iter_mut().zip(z_values) {
+        let tied = depths
+            .iter()
+            .filter(|depth| (*depth - hit.depth).abs() <= DEPTH_TIE)
+            .count()
+            > 1;
+        if tied {
+            hit.depth -= z * DEPTH_TIE;
+        }
+    }
+}
+
+/// `pick_order` of the nearest ancestor [`RectrayFrame`] of an entity.
+fn frame_pick_order(
+    entity: Entity,
+    parents: &Query<&Parent>,
+    frames: &Query<&RectrayFrame>,
+) -> f32 {
+    parents
+        .iter_ancestors(entity)
+        .find_map(|parent| frames.get(parent).ok())
+        .map_or(0.0, |frame| frame.pick_order)
+}
+
+/// Group hits of a ray into one [`PointerHits`] per distinct `order`,
+/// which is the camera's `order` plus the `pick_order` of each hit.
+///
+/// Hits are given with their [`RotatedRect::z`].
+fn group_hits(
+    pointer: PointerId,
+    camera_order: f32,
+    hits: impl IntoIterator<Item = (Entity, HitData, f32)>,
+    pick_order: impl Fn(Entity) -> f32,
+) -> Vec<PointerHits> {
+    let mut events: Vec<(PointerHits, Vec<f32>)> = Vec::new();
+    for (entity, hit, z) in hits {
+        let order = camera_order + pick_order(entity);
+        let index = match events.iter().position(|(event, _)| event.order == order) {
+            Some(index) => index,
+            None => {
+                events.push((PointerHits::new(pointer, Vec::new(), order), Vec::new()));
+                events.len() - 1
+            }
+        };
+        let (event, z_values) = &mut events[index];
+        event.picks.push((entity, hit));
+        z_values.push(z);
+    }
+    events
+        .into_iter()
+        .map(|(mut event, z_values)| {
+            bias_tied_depths(&mut event.picks, &z_values);
+            event
+        })
+        .collect()
+}
+
 /// System for the backed.
+///
+/// Cameras rendering to an image are supported, since rays are created by `RayMap`
+/// for any pointer whose location targets the camera's render target,
+/// see [`RectrayRenderTargetPointer`] for creating such pointers.
+///
+/// Hits are grouped by [`RectrayFrame::pick_order`] of their nearest ancestor frame,
+/// each group is sent with the camera's `order` plus `pick_order`.
 pub fn rectray_picking_backend(
     map: Res<RayMap>,
     layers: Query<(Option<&RenderLayers>, &Camera)>,
-    query: Query<
+    mut query: Query<
         (
             Entity,
             &RotatedRect,
             &GlobalTransform,
-            &Transform2D,
+            &CachedInverseTransform,
             Option<&RenderLayers>,
+            Option<&PickShape>,
+            Option<&mut RectrayPickData>,
         ),
-        With<RectrayPickable>,
+        (With<RectrayPickable>, Without<PickingDisabled>),
     >,
+    grid: Option<Res<RectrayPickingGrid>>,
+    frames: Query<&GlobalTransform>,
+    parents: Query<&Parent>,
+    frame_orders: Query<&RectrayFrame>,
     mut writer: EventWriter<PointerHits>,
 ) {
+    let grid = grid.filter(|grid| !grid.is_empty());
+    let mut candidates = Vec::new();
+    let mut hits = Vec::new();
     for (ray_id, ray) in map.iter() {
         let Ok((layer, cam)) = layers.get(ray_id.camera) else {
             continue;
@@ -62,12 +608,17 @@ pub fn rectray_picking_backend(
         } else {
             &RenderLayers::default()
         };
-        let mut event = PointerHits {
-            pointer: ray_id.pointer,
-            picks: Vec::new(),
-            order: cam.order as f32,
-        };
-        for (entity, rect, transform, transform_2d, layers) in query.iter() {
+        candidates.clear();
+        match &grid {
+            Some(grid) => grid.candidates(*ray, &frames, &mut candidates),
+            None => candidates.extend(query.iter().map(|(entity, ..)| entity)),
+        }
+        for candidate in candidates.iter().copied() {
+            let Ok((entity, rect, transform, inverse, layers, shape, data)) =
+                query.get_mut(candidate)
+            else {
+                continue;
+            };
             let layer = if let Some(layer) = layers {
                 layer
             } else {
@@ -76,33 +627,149 @@ pub fn rectray_picking_backend(
             if !cam_layer.intersects(layer) {
                 continue;
             }
-            let plane = InfinitePlane3d::new(transform.forward());
-            let Some(depth) = ray.intersect_plane(transform.translation(), plane) else {
+            let Some(hit) = ray_cast_rect_with_inverse(*ray, transform, &inverse.0, rect) else {
                 continue;
             };
-            let position = ray.get_point(depth);
-            let local = transform.affine().inverse().transform_point3(position);
-            let local = local.xy() - rect.dimension * transform_2d.center;
-            let half_size = rect.dimension * rect.scale / 2.0;
-            let inside = Vec2::from_angle(-rect.rotation)
-                .rotate(local)
-                .abs()
-                .cmple(half_size)
-                .all();
-            if inside {
-                event.picks.push((
+            // Cheap rejection against the bounds of the rect in local space,
+            // before testing the shape.
+            if !hit.is_inside() {
+                continue;
+            }
+            let local = hit.local();
+            if shape.is_none_or(|shape| shape.contains(local, rect.dimension)) {
+                if let Some(mut data) = data {
+                    data.local = local;
+                    data.uv = local / rect.dimension + 0.5;
+                }
+                hits.push((
                     entity,
                     HitData {
                         camera: ray_id.camera,
-                        depth,
-                        position: Some(position),
+                        depth: hit.depth,
+                        position: Some(hit.position),
                         normal: Some(transform.forward().into()),
                     },
-                ))
+                    rect.z,
+                ));
             }
         }
-        if !event.picks.is_empty() {
+        let pick_order = |entity| frame_pick_order(entity, &parents, &frame_orders);
+        for event in group_hits(ray_id.pointer, cam.order as f32, hits.drain(..), pick_order) {
             writer.send(event);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::{RunSystemOnce, SystemState};
+    use bevy::ecs::world::World;
+    use bevy::hierarchy::BuildChildren;
+    use bevy::math::Dir3;
+
+    use super::*;
+    use crate::ScaleMode;
+
+    fn ray_at(point: Vec2) -> Ray3d {
+        Ray3d::new(point.extend(10.0), Dir3::NEG_Z)
+    }
+
+    #[test]
+    fn grid_bins_descendants_in_frame_layout_space() {
+        let mut world = World::new();
+        world.insert_resource(RectrayPickingGrid::new(64.0));
+        let pickable = |world: &mut World, position: Vec2| {
+            world
+                .spawn((
+                    RectrayPickable,
+                    RotatedRect {
+                        dimension: Vec2::new(10.0, 10.0),
+                        scale: Vec2::ONE,
+                        ..Default::default()
+                    },
+                    GlobalTransform::from_translation(position.extend(0.0)),
+                ))
+                .id()
+        };
+        let near = pickable(&mut world, Vec2::new(100.0, 60.0));
+        let far = pickable(&mut world, Vec2::new(-300.0, -200.0));
+        // Children of a container are not direct children of the frame.
+        let container = world
+            .spawn(GlobalTransform::from_translation(Vec3::new(
+                80.0, 50.0, 0.0,
+            )))
+            .add_children(&[near, far])
+            .id();
+        let scale = Transform::from_scale(Vec3::new(2.0, 2.0, 1.0));
+        world
+            .spawn((
+                RectrayFrame {
+                    scale_mode: ScaleMode::LayoutSpace,
+                    ..RectrayFrame::from_dimension(Vec2::new(400.0, 300.0))
+                },
+                scale,
+                GlobalTransform::from(scale),
+            ))
+            .add_child(container);
+        world.run_system_once(build_picking_grid).unwrap();
+
+        let mut state = SystemState::<Query<&GlobalTransform>>::new(&mut world);
+        let frames = state.get(&world);
+        let grid = world.resource::<RectrayPickingGrid>();
+        let mut candidates = Vec::new();
+        grid.candidates(ray_at(Vec2::new(103.0, 57.0)), &frames, &mut candidates);
+        assert_eq!(candidates, vec![near]);
+        candidates.clear();
+        grid.candidates(ray_at(Vec2::new(-300.0, -200.0)), &frames, &mut candidates);
+        assert_eq!(candidates, vec![far]);
+        candidates.clear();
+        grid.candidates(ray_at(Vec2::new(300.0, -200.0)), &frames, &mut candidates);
+        assert!(candidates.is_empty());
+    }
+
+    #[test]
+    fn hits_are_grouped_by_frame_pick_order() {
+        let mut world = World::new();
+        let base_item = world.spawn_empty().id();
+        let overlay_item = world.spawn_empty().id();
+        let nested_item = world.spawn_empty().id();
+        let container = world.spawn_empty().add_child(nested_item).id();
+        world
+            .spawn(RectrayFrame::from_dimension(Vec2::new(100.0, 100.0)))
+            .add_child(base_item);
+        world
+            .spawn(RectrayFrame {
+                pick_order: 10.0,
+                ..RectrayFrame::from_dimension(Vec2::new(100.0, 100.0))
+            })
+            .add_children(&[overlay_item, container]);
+        let orphan = world.spawn_empty().id();
+
+        let mut state = SystemState::<(Query<&Parent>, Query<&RectrayFrame>)>::new(&mut world);
+        let (parents, frames) = state.get(&world);
+        let camera = Entity::PLACEHOLDER;
+        // The base item is in front, but the overlay frame is picked first.
+        let hit = |depth| HitData::new(camera, depth, None, None);
+        let hits = [
+            (base_item, hit(1.0), 0.0),
+            (overlay_item, hit(5.0), 0.0),
+            (nested_item, hit(6.0), 0.0),
+            (orphan, hit(2.0), 0.0),
+        ];
+        let events = group_hits(PointerId::Mouse, 1.0, hits, |entity| {
+            frame_pick_order(entity, &parents, &frames)
+        });
+        assert_eq!(events.len(), 2);
+        let picked = |order: f32| {
+            let event = events.iter().find(|event| event.order == order).unwrap();
+            assert_eq!(event.pointer, PointerId::Mouse);
+            event
+                .picks
+                .iter()
+                .map(|(entity, _)| *entity)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(picked(1.0), vec![base_item, orphan]);
+        assert_eq!(picked(11.0), vec![overlay_item, nested_item]);
+    }
+}
@@ -1,33 +1,143 @@
-use std::mem;
+use std::{iter, mem};
 
 use bevy::ecs::{
-    entity::Entity,
-    system::{Local, Query},
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    event::{Event, EventWriter},
+    query::{Changed, Or, QueryData, With, Without},
+    removal_detection::RemovedComponents,
+    system::{Commands, In, Local, ParamSet, Query, SystemParam, SystemState},
+    world::World,
 };
-use bevy::hierarchy::Children;
+use bevy::hierarchy::{Children, HierarchyQueryExt, Parent};
+use bevy::log::warn_once;
+use bevy::math::{Vec2, Vec3Swizzles};
+use bevy::prelude::Visibility;
+use bevy::tasks::{ComputeTaskPool, ParallelSlice, TaskPool};
 use bevy::transform::components::Transform;
 
 use crate::{
-    hierarchy::RectrayFrame,
-    layout::{Container, LayoutControl, LayoutInfo, LayoutItem, LayoutOutput},
+    hierarchy::{RectrayFrame, ScaleMode},
+    layout::{
+        ClipState, Container, FlexGrow, FlexShrink, GridSpan, LayoutControl, LayoutItem,
+        LayoutOutput, LayoutSortKey, RectrayClip, RectrayCull, TextBaseline, TextDirection,
+    },
     rect::{ParentInfo, RotatedRect},
-    transform::{Dimension, Transform2D},
+    transform::{
+        AspectRatio, Dimension, DimensionConstraints, DimensionPercent, NinePatchFrame, PixelSnap,
+        RotationSnap, Transform2D, ZIndex,
+    },
 };
 
 type REntity<'t> = (Entity, &'t Dimension, &'t Transform2D, &'t LayoutControl);
 
-#[allow(clippy::too_many_arguments)]
-#[allow(clippy::needless_pass_by_ref_mut)]
+/// Optional components of a child that modifies its [`LayoutItem`].
+#[doc(hidden)]
+#[derive(QueryData)]
+pub struct RItem {
+    grow: Option<&'static FlexGrow>,
+    shrink: Option<&'static FlexShrink>,
+    span: Option<&'static GridSpan>,
+    sort_key: Option<&'static LayoutSortKey>,
+    percent: Option<&'static DimensionPercent>,
+    constraints: Option<&'static DimensionConstraints>,
+    aspect: Option<&'static AspectRatio>,
+    direction: Option<&'static TextDirection>,
+    baseline: Option<&'static TextBaseline>,
+    z_index: Option<&'static ZIndex>,
+    rotation_snap: Option<&'static RotationSnap>,
+}
+
+impl RItemItem<'_> {
+    /// Resolve the effective dimension of an item in a parent of dimension `parent`.
+    fn dimension(&self, dimension: Vec2, parent: Vec2) -> Vec2 {
+        let dimension = self
+            .percent
+            .map_or(dimension, |x| x.resolve(dimension, parent));
+        let dimension = self.constraints.map_or(dimension, |x| x.resolve(dimension));
+        self.aspect.map_or(dimension, |x| x.resolve(dimension))
+    }
+}
+
+/// Writes produced by propagating subtrees,
+/// applied after all subtrees are computed.
+#[derive(Debug, Default)]
+struct PropagateOutput {
+    rects: Vec<(Entity, Transform, RotatedRect)>,
+    containers: Vec<(Entity, Container)>,
+    clip_states: Vec<(Entity, ClipState)>,
+    /// Children of [`RectrayCull`] containers and whether they are placed.
+    culled: Vec<(Entity, bool)>,
+    /// Children of nested frames with [`ScaleMode::LayoutSpace`] and the frame's scale.
+    layout_scales: Vec<(Entity, Vec2)>,
+}
+
+/// Read only queries used by [`propagate`].
+#[derive(Clone, Copy)]
+struct PropagateQueries<'a, 'w, 's> {
+    entity_query: &'a Query<'w, 's, REntity<'static>>,
+    item_query: &'a Query<'w, 's, RItem>,
+    layout_query: &'a Query<'w, 's, &'static Container>,
+    child_query: &'a Query<'w, 's, &'static Children>,
+    clip_query: &'a Query<'w, 's, (), With<RectrayClip>>,
+    cull_query: &'a Query<'w, 's, (), With<RectrayCull>>,
+    frame_query: &'a Query<'w, 's, &'static RectrayFrame>,
+    frame_transform_query:
+        &'a Query<'w, 's, &'static Transform, (With<RectrayFrame>, Without<RotatedRect>)>,
+}
+
+/// Replace NaN and infinite components of a dimension with `0`,
+/// so one bad widget does not break the whole frame.
+fn sanitize_dimension(entity: Entity, dimension: Vec2) -> Vec2 {
+    if dimension.is_finite() {
+        return dimension;
+    }
+    warn_once!("Entity {entity} has a non-finite dimension {dimension}, replaced with 0.");
+    Vec2::select(dimension.is_finite_mask(), dimension, Vec2::ZERO)
+}
+
 fn propagate(
     parent: ParentInfo,
     entity: Entity,
-    mut_query: &mut Query<REntity>,
-    layout_query: &mut Query<&mut Container>,
-    child_query: &Query<&Children>,
+    queries: PropagateQueries,
     queue: &mut Vec<(Entity, ParentInfo)>,
-    transform_query: &mut Query<(&mut Transform, &mut RotatedRect)>,
+    output: &mut PropagateOutput,
 ) {
+    let PropagateQueries {
+        entity_query: mut_query,
+        item_query,
+        layout_query,
+        child_query,
+        clip_query,
+        cull_query,
+        frame_query,
+        frame_transform_query,
+    } = queries;
+
     if !mut_query.contains(entity) {
+        // A nested frame starts a new layout space in the parent's rect.
+        if let Ok(frame) = frame_query.get(entity) {
+            // Same as `RectrayPipeline::push_frame`.
+            let scale = frame_transform_query
+                .get(entity)
+                .ok()
+                .and_then(|transform| frame.layout_scale(transform));
+            let info = ParentInfo {
+                dimension: parent.dimension,
+                center: frame.center * scale.unwrap_or(Vec2::ONE),
+                at: frame.at,
+                anchor: None,
+                child_dimension: None,
+                clip: None,
+            };
+            if let Ok(children) = child_query.get(entity) {
+                if let Some(scale) = scale {
+                    output
+                        .layout_scales
+                        .extend(children.iter().map(|child| (*child, scale)));
+                }
+                queue.extend(children.iter().map(|child| (*child, info)));
+            }
+        }
         return;
     }
 
@@ -35,9 +145,26 @@ fn propagate(
         return;
     };
 
-    let dimension = dim.0;
+    let dimension = sanitize_dimension(
+        entity,
+        parent
+            .child_dimension
+            .unwrap_or_else(|| match item_query.get(entity) {
+                Ok(item) => item.dimension(dim.0, parent.dimension),
+                Err(_) => dim.0,
+            }),
+    );
+    let z_bias = item_query
+        .get(entity)
+        .ok()
+        .and_then(|item| item.z_index)
+        .map_or(0.0, |z_index| z_index.bias());
+    let rotation_snap = item_query
+        .get(entity)
+        .ok()
+        .and_then(|item| item.rotation_snap);
 
-    if let Ok(mut layout) = layout_query.get_mut(entity) {
+    if let Ok(layout) = layout_query.get(entity) {
         let children = child_query
             .get(entity)
             .map(|x| x.iter().copied())
@@ -45,8 +172,13 @@ fn propagate(
             .flatten();
         let mut other_entities = Vec::new();
         let mut args = Vec::new();
+        let mut sorted = false;
+        let mut overlay_size = Vec2::ZERO;
         for child in children {
             if !mut_query.contains(child) {
+                if frame_query.contains(child) {
+                    other_entities.push((child, Vec2::ZERO));
+                }
                 continue;
             }
 
@@ -55,107 +187,842 @@ fn propagate(
                     LayoutControl::IgnoreLayout => {
                         other_entities.push((child, child_transform.get_parent_anchor()))
                     }
+                    LayoutControl::Overlay => {
+                        let child_dimension = sanitize_dimension(
+                            child,
+                            match item_query.get(child) {
+                                Ok(item) => item.dimension(child_dim.0, dimension),
+                                Err(_) => child_dim.0,
+                            },
+                        );
+                        overlay_size = overlay_size.max(
+                            child_dimension * child_transform.scale.abs()
+                                + child_transform.get_offset().abs(),
+                        );
+                        other_entities.push((child, child_transform.get_parent_anchor()))
+                    }
                     control => {
-                        args.push(LayoutItem {
-                            entity: child,
-                            anchor: child_transform.get_parent_anchor(),
-                            dimension: child_dim.0,
-                            control: *control,
-                        });
+                        let Ok(item) = item_query.get(child) else {
+                            continue;
+                        };
+                        let child_dimension =
+                            sanitize_dimension(child, item.dimension(child_dim.0, dimension));
+                        if layout.skip_unmeasured && child_dimension == Vec2::ZERO {
+                            other_entities.push((child, child_transform.get_parent_anchor()));
+                            continue;
+                        }
+                        sorted |= item.sort_key.is_some();
+                        args.push((
+                            item.sort_key.map_or(0, |x| x.0),
+                            LayoutItem {
+                                entity: child,
+                                anchor: child_transform.get_parent_anchor(),
+                                own_anchor: child_transform.anchor.as_vec(),
+                                offset: child_transform.get_offset(),
+                                dimension: child_dimension,
+                                control: *control,
+                                grow: item.grow.map_or(0.0, |x| x.0),
+                                shrink: item.shrink.map_or(1.0, |x| x.0),
+                                span: item.span.copied().unwrap_or_default(),
+                                direction: item.direction.copied().unwrap_or_default(),
+                                baseline: item.baseline.map(|x| x.0),
+                            },
+                        ));
                     }
                 };
             }
         }
-        let margin = layout.margin;
+        if sorted {
+            args.sort_by_key(|(key, _)| *key);
+        }
+        let args: Vec<_> = args.into_iter().map(|(_, item)| item).collect();
+        let items: Vec<_> = if cull_query.contains(entity) {
+            args.iter().map(|item| item.entity).collect()
+        } else {
+            Vec::new()
+        };
+        let mut layout = layout.clone();
         let LayoutOutput {
             mut entity_anchors,
-            dimension: new_dim,
-            max_count,
-        } = layout.place(&LayoutInfo { dimension, margin }, args);
-        layout.maximum = max_count;
-        let padding = layout.padding * 2.0;
-        let fac = new_dim / (new_dim + padding);
-        let size = new_dim + padding;
-        if !fac.is_nan() {
+            dimension: content_dim,
+            entity_dimensions,
+            ..
+        } = layout.compute(dimension, args);
+        let placed: EntityHashSet = entity_anchors.iter().map(|(e, _)| *e).collect();
+        output
+            .culled
+            .extend(items.into_iter().map(|e| (e, placed.contains(&e))));
+        let new_dim = sanitize_dimension(entity, content_dim).max(overlay_size);
+        if new_dim != content_dim {
+            // Anchors are relative to the content box, centered on `padding.offset()`,
+            // rescale them against the content box without padding to keep items in place.
+            let fac = content_dim / new_dim;
+            // `0 / 0` on an empty axis.
+            let fac = Vec2::select(fac.is_nan_mask(), Vec2::ONE, fac);
             entity_anchors.iter_mut().for_each(|(_, anc)| *anc *= fac);
+            layout.dividers.iter_mut().for_each(|(anc, _)| *anc *= fac);
+        }
+        let size = new_dim + layout.padding.size();
+        let mut rect = RotatedRect::construct(&parent, transform, size);
+        rect.z += z_bias;
+        if let Some(snap) = rotation_snap {
+            rect.rotation = snap.snap(rect.rotation);
         }
-        let rect = RotatedRect::construct(&parent, transform, size);
 
         let info = ParentInfo {
             dimension: new_dim,
+            center: layout.padding.offset(),
             at: transform.get_center(),
             anchor: None,
+            child_dimension: None,
+            clip: clip_query.contains(entity).then_some(RotatedRect {
+                dimension: size,
+                scale: Vec2::ONE,
+                ..Default::default()
+            }),
         };
+        output.containers.push((entity, layout));
 
-        queue.extend(
-            entity_anchors
-                .into_iter()
-                .map(|(e, anc)| (e, info.with_anchor(anc))),
-        );
-        if let Ok((mut a, mut b)) = transform_query.get_mut(entity) {
-            *b = rect;
-            *a = rect.transform_at(transform.get_center());
-        }
+        let entity_dimensions: EntityHashMap<_> = entity_dimensions.into_iter().collect();
+        queue.extend(entity_anchors.into_iter().map(|(e, anc)| {
+            (
+                e,
+                info.with_anchor(anc)
+                    .with_child_dimension(entity_dimensions.get(&e).copied()),
+            )
+        }));
+        output
+            .rects
+            .push((entity, rect.transform_at(transform.get_center()), rect));
+        push_clip_state(&parent, entity, &rect, transform, output);
         for (child, _) in other_entities {
             queue.push((child, info))
         }
         return;
     }
 
-    let rect = RotatedRect::construct(&parent, transform, dimension);
+    let mut rect = RotatedRect::construct(&parent, transform, dimension);
+    rect.z += z_bias;
+    if let Some(snap) = rotation_snap {
+        rect.rotation = snap.snap(rect.rotation);
+    }
 
     if let Ok(children) = child_query.get(entity) {
         let info = ParentInfo {
             dimension,
+            center: Vec2::ZERO,
             anchor: None,
             at: transform.get_center(),
+            child_dimension: None,
+            clip: None,
         };
         for child in children.iter().copied() {
             queue.push((child, info))
         }
     }
 
-    if let Ok((mut a, mut b)) = transform_query.get_mut(entity) {
-        *a = rect.transform_at(transform.get_center());
-        *b = rect;
-    }
+    output
+        .rects
+        .push((entity, rect.transform_at(transform.get_center()), rect));
+    push_clip_state(&parent, entity, &rect, transform, output);
 }
 
-/// The main computation step.
-pub fn compute_transform_2d(
-    mut queue_a: Local<Vec<(Entity, ParentInfo)>>,
-    mut queue_b: Local<Vec<(Entity, ParentInfo)>>,
-    root_query: Query<(&RectrayFrame, &Children)>,
-    mut entity_query: Query<REntity>,
-    mut layout_query: Query<&mut Container>,
-    child_query: Query<&Children>,
-    mut transform_query: Query<(&mut Transform, &mut RotatedRect)>,
+/// Classify an item against the rect of a [`RectrayClip`] parent.
+fn push_clip_state(
+    parent: &ParentInfo,
+    entity: Entity,
+    rect: &RotatedRect,
+    transform: &Transform2D,
+    output: &mut PropagateOutput,
 ) {
-    for (root, children) in root_query.iter() {
+    let Some(clip) = &parent.clip else {
+        return;
+    };
+    // `RotatedRect::center` is the pivot, move it to the center of the rect.
+    let rect = RotatedRect {
+        center: rect.transform_at(transform.get_center()).translation.xy(),
+        ..*rect
+    };
+    output
+        .clip_states
+        .push((entity, ClipState::classify(clip, &rect)));
+}
+
+/// Propagate subtrees starting from `seeds` breadth first.
+fn propagate_subtrees(
+    seeds: &[(Entity, ParentInfo)],
+    queries: PropagateQueries,
+) -> PropagateOutput {
+    let mut output = PropagateOutput::default();
+    let mut queue_a = seeds.to_vec();
+    let mut queue_b = Vec::new();
+    while !queue_a.is_empty() {
+        mem::swap(&mut queue_a, &mut queue_b);
+        for (entity, parent) in queue_b.drain(..) {
+            propagate(parent, entity, queries, &mut queue_a, &mut output);
+        }
+    }
+    output
+}
+
+/// Compute the dimension of a widget's rect from its components and children,
+/// the same way as the pipeline, without writing to the world.
+///
+/// Useful for sizing things like tooltips to their content before committing them.
+/// Stretching by the widget's parent container is not applied,
+/// [`DimensionPercent`] resolves against the content box of the parent as of the last layout.
+/// Returns [`Vec2::ZERO`] if `entity` is not a widget.
+pub fn measure_subtree(world: &mut World, entity: Entity) -> Vec2 {
+    let mut state = SystemState::<(
+        Query<REntity<'static>>,
+        Query<RItem>,
+        Query<&Container>,
+        Query<&Children>,
+        Query<(), With<RectrayClip>>,
+        Query<(), With<RectrayCull>>,
+        Query<&RectrayFrame>,
+        Query<&Transform, (With<RectrayFrame>, Without<RotatedRect>)>,
+        Query<&Parent>,
+        Query<&RotatedRect>,
+    )>::new(world);
+    let (
+        entity_query,
+        item_query,
+        layout_query,
+        child_query,
+        clip_query,
+        cull_query,
+        frame_query,
+        frame_transform_query,
+        parent_query,
+        rect_query,
+    ) = state.get(world);
+    let parent_dimension = |parent: Entity| {
+        if let Ok(frame) = frame_query.get(parent) {
+            // Nested frames are laid out in the rect of their parent.
+            let outer = parent_query
+                .get(parent)
+                .and_then(|outer| rect_query.get(outer.get()));
+            if let Ok(rect) = outer {
+                return rect.dimension;
+            }
+            let scale = frame_transform_query
+                .get(parent)
+                .ok()
+                .and_then(|transform| frame.layout_scale(transform));
+            return frame.dimension * scale.unwrap_or(Vec2::ONE);
+        }
+        let padding = layout_query
+            .get(parent)
+            .map_or(Vec2::ZERO, |layout| layout.padding.size());
+        rect_query
+            .get(parent)
+            .map_or(Vec2::ZERO, |rect| rect.dimension - padding)
+    };
+    let dimension = parent_query
+        .get(entity)
+        .map_or(Vec2::ZERO, |parent| parent_dimension(parent.get()));
+    let queries = PropagateQueries {
+        entity_query: &entity_query,
+        item_query: &item_query,
+        layout_query: &layout_query,
+        child_query: &child_query,
+        clip_query: &clip_query,
+        cull_query: &cull_query,
+        frame_query: &frame_query,
+        frame_transform_query: &frame_transform_query,
+    };
+    let parent = ParentInfo {
+        dimension,
+        center: Vec2::ZERO,
+        at: Vec2::ZERO,
+        anchor: None,
+        child_dimension: None,
+        clip: None,
+    };
+    let mut output = PropagateOutput::default();
+    // The rect only depends on components of direct children, descendants are not propagated.
+    propagate(parent, entity, queries, &mut Vec::new(), &mut output);
+    output
+        .rects
+        .iter()
+        .find(|(e, ..)| *e == entity)
+        .map_or(Vec2::ZERO, |(.., rect)| rect.dimension)
+}
+
+/// Event sent when the `maximum` or `content_size` of a [`Container`] changes after layout.
+#[derive(Debug, Clone, Copy, PartialEq, Event)]
+pub struct RectrayLayoutChanged {
+    /// The entity with the [`Container`].
+    pub entity: Entity,
+    /// New value of [`Container::maximum`].
+    pub maximum: usize,
+    /// New value of [`Container::content_size`].
+    pub content_size: Vec2,
+}
+
+/// System parameters of the `bevy_rectray` pipeline.
+#[doc(hidden)]
+#[derive(SystemParam)]
+pub struct RectrayPipeline<'w, 's> {
+    seeds: Local<'s, Vec<(Entity, ParentInfo)>>,
+    /// Direct children of frames with [`ScaleMode::LayoutSpace`] and the frame's scale.
+    layout_scales: Local<'s, EntityHashMap<Vec2>>,
+    entity_query: Query<'w, 's, REntity<'static>>,
+    item_query: Query<'w, 's, RItem>,
+    layout_query: Query<'w, 's, &'static mut Container>,
+    child_query: Query<'w, 's, &'static Children>,
+    transform_query: Query<'w, 's, (&'static mut Transform, &'static mut RotatedRect)>,
+    frame_transform_query:
+        Query<'w, 's, &'static Transform, (With<RectrayFrame>, Without<RotatedRect>)>,
+    frame_query: Query<'w, 's, &'static RectrayFrame>,
+    parent_query: Query<'w, 's, &'static Parent>,
+    clip_query: Query<'w, 's, (), With<RectrayClip>>,
+    clip_state_query: Query<'w, 's, &'static mut ClipState>,
+    cull_query: Query<'w, 's, (), With<RectrayCull>>,
+    visibility_query: Query<'w, 's, &'static mut Visibility>,
+    layout_changed: EventWriter<'w, RectrayLayoutChanged>,
+    commands: Commands<'w, 's>,
+}
+
+impl RectrayPipeline<'_, '_> {
+    /// Queue children of a frame.
+    ///
+    /// Children are seeded with the frame's `dimension`, `center` and `at`,
+    /// multiplied by the frame's scale if [`ScaleMode::LayoutSpace`] is used.
+    fn push_frame(&mut self, frame: Entity, root: &RectrayFrame, children: &Children) {
+        // Transforms of nested frames are computed by the pipeline.
+        let scale = match self.frame_transform_query.get(frame) {
+            Ok(transform) => root.layout_scale(transform),
+            Err(_) => match self.transform_query.get(frame) {
+                Ok((transform, _)) => root.layout_scale(transform),
+                Err(_) => None,
+            },
+        };
+        let layout_scale = scale.unwrap_or(Vec2::ONE);
+        // Nested frames are laid out in the rect of their parent.
+        let dimension = self
+            .parent_query
+            .get(frame)
+            .and_then(|parent| self.transform_query.get(parent.get()))
+            .map_or(root.dimension * layout_scale, |(_, rect)| rect.dimension);
         for child in children.iter().copied() {
-            queue_a.push((
+            if let Some(scale) = scale {
+                self.layout_scales.insert(child, scale);
+            }
+            self.seeds.push((
                 child,
                 ParentInfo {
-                    dimension: root.dimension,
+                    dimension,
+                    center: root.center * layout_scale,
                     at: root.at,
                     anchor: None,
+                    child_dimension: None,
+                    clip: None,
                 },
             ))
         }
     }
 
-    while !queue_a.is_empty() {
-        mem::swap::<Vec<_>>(queue_a.as_mut(), queue_b.as_mut());
-        for (entity, parent) in queue_b.drain(..) {
-            propagate(
-                parent,
-                entity,
-                &mut entity_query,
-                &mut layout_query,
-                &child_query,
-                &mut queue_a,
-                &mut transform_query,
-            );
+    /// Propagate queued entities and their descendants.
+    ///
+    /// Subtrees are disjoint so they are computed in parallel on the [`ComputeTaskPool`],
+    /// writes are collected and applied afterwards.
+    fn run(&mut self) {
+        self.run_in_tasks(None);
+    }
+
+    /// [`Self::run`] split into at most `max_tasks` tasks, one task runs on a single thread.
+    fn run_in_tasks(&mut self, max_tasks: Option<usize>) {
+        if self.seeds.is_empty() {
+            return;
+        }
+        let layout_query = self.layout_query.to_readonly();
+        let queries = PropagateQueries {
+            entity_query: &self.entity_query,
+            item_query: &self.item_query,
+            layout_query: &layout_query,
+            child_query: &self.child_query,
+            clip_query: &self.clip_query,
+            cull_query: &self.cull_query,
+            frame_query: &self.frame_query,
+            frame_transform_query: &self.frame_transform_query,
+        };
+        let outputs = self.seeds.par_splat_map(
+            ComputeTaskPool::get_or_init(TaskPool::default),
+            max_tasks,
+            |_, seeds| propagate_subtrees(seeds, queries),
+        );
+        self.seeds.clear();
+        for output in outputs {
+            self.apply(output);
+        }
+        self.layout_scales.clear();
+    }
+
+    /// Undo the effects of [`RectrayCull`] and [`RectrayClip`] on children of entities
+    /// they were removed from.
+    fn clean_up_removed(
+        &mut self,
+        culled: impl IntoIterator<Item = Entity>,
+        clipped: impl IntoIterator<Item = Entity>,
+    ) {
+        // Components could be removed and added again in the same frame.
+        for entity in culled.into_iter().filter(|e| !self.cull_query.contains(*e)) {
+            let Ok(children) = self.child_query.get(entity) else {
+                continue;
+            };
+            for child in children.iter().copied() {
+                let Ok(mut visibility) = self.visibility_query.get_mut(child) else {
+                    continue;
+                };
+                if *visibility == Visibility::Hidden {
+                    *visibility = Visibility::Inherited;
+                }
+            }
+        }
+        for entity in clipped
+            .into_iter()
+            .filter(|e| !self.clip_query.contains(*e))
+        {
+            let Ok(children) = self.child_query.get(entity) else {
+                continue;
+            };
+            for child in children.iter().copied() {
+                if self.clip_state_query.contains(child) {
+                    self.commands.entity(child).remove::<ClipState>();
+                }
+            }
+        }
+    }
+
+    /// Write the output of [`propagate_subtrees`].
+    fn apply(&mut self, output: PropagateOutput) {
+        self.layout_scales.extend(output.layout_scales);
+        for (entity, mut transform, rect) in output.rects {
+            if let Some(scale) = self.layout_scales.get(&entity) {
+                // Cancel out the scale of the frame.
+                transform.translation /= scale.extend(1.0);
+                transform.scale /= scale.extend(1.0);
+            }
+            if let Ok((mut a, mut b)) = self.transform_query.get_mut(entity) {
+                *a = transform;
+                *b = rect;
+            }
         }
+        for (entity, computed) in output.containers {
+            let Ok(mut container) = self.layout_query.get_mut(entity) else {
+                continue;
+            };
+            // Only mark the container as changed if runtime computed fields changed,
+            // so unchanged frames can be skipped.
+            if container.range != computed.range
+                || container.maximum != computed.maximum
+                || container.content_size != computed.content_size
+                || container.column_widths != computed.column_widths
+                || container.dividers != computed.dividers
+                || container.items != computed.items
+                || container.placed != computed.placed
+            {
+                if container.maximum != computed.maximum
+                    || container.content_size != computed.content_size
+                {
+                    self.layout_changed.send(RectrayLayoutChanged {
+                        entity,
+                        maximum: computed.maximum,
+                        content_size: computed.content_size,
+                    });
+                }
+                container.range = computed.range;
+                container.maximum = computed.maximum;
+                container.content_size = computed.content_size;
+                container.column_widths = computed.column_widths;
+                container.dividers = computed.dividers;
+                container.items = computed.items;
+                container.placed = computed.placed;
+            }
+        }
+        for (entity, placed) in output.culled {
+            let Ok(mut visibility) = self.visibility_query.get_mut(entity) else {
+                continue;
+            };
+            let expected = if placed {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            if *visibility != expected {
+                *visibility = expected;
+            }
+        }
+        for (entity, state) in output.clip_states {
+            if let Ok(mut current) = self.clip_state_query.get_mut(entity) {
+                if *current != state {
+                    *current = state;
+                }
+            } else {
+                self.commands.entity(entity).insert(state);
+            }
+        }
+    }
+}
+
+/// Entities with inputs of the pipeline changed.
+type RChanged = Or<(
+    Changed<RectrayFrame>,
+    Changed<Transform2D>,
+    Changed<Dimension>,
+    Changed<Container>,
+    Changed<Children>,
+    Changed<Parent>,
+    Changed<LayoutControl>,
+    Changed<FlexGrow>,
+    Or<(Changed<FlexShrink>, Changed<GridSpan>)>,
+    Changed<LayoutSortKey>,
+    Changed<DimensionConstraints>,
+    Or<(Changed<AspectRatio>, Changed<DimensionPercent>)>,
+    Changed<TextDirection>,
+    Changed<TextBaseline>,
+    Or<(
+        Changed<RectrayClip>,
+        Changed<RectrayCull>,
+        Changed<ZIndex>,
+        Changed<RotationSnap>,
+    )>,
+)>;
+
+/// The main computation step.
+///
+/// Only frames with changed descendants are computed, a frame is changed if any
+/// of its descendants or itself has a changed [`Transform2D`], [`Dimension`], [`Container`],
+/// `Children`, `Parent` or other optional components read by the pipeline.
+/// Removal of `Children` or `Parent` also marks the entity as changed,
+/// removal of other optional components is not detected, use [`compute_frame`]
+/// or mark a component as changed in that case.
+///
+/// When [`RectrayCull`] is removed, hidden children are made visible again,
+/// when [`RectrayClip`] is removed, [`ClipState`] is removed from children.
+///
+/// Children of a frame are seeded with a parent of the frame's `dimension`, or `dimension`
+/// multiplied by the frame's `Transform.scale` if [`ScaleMode::LayoutSpace`] is used,
+/// in which case a changed `Transform` also marks the frame as changed.
+///
+/// A frame without [`Transform2D`] that is a child of an item of another frame is a nested frame,
+/// its children are laid out in the rect of its parent, ignoring its own `dimension`.
+/// The `Transform` of a nested frame is not modified, so it is placed at the center of its parent by default.
+pub fn compute_transform_2d(
+    mut dirty: Local<EntityHashSet>,
+    scaled: Query<(Entity, &RectrayFrame), (Without<RotatedRect>, Changed<Transform>)>,
+    parent_query: Query<&Parent>,
+    root_query: Query<(Entity, &RectrayFrame, &Children)>,
+    mut removed_children: RemovedComponents<Children>,
+    mut removed_parents: RemovedComponents<Parent>,
+    mut removed_cull: RemovedComponents<RectrayCull>,
+    mut removed_clip: RemovedComponents<RectrayClip>,
+    // `RChanged` reads `Container` which is written by the pipeline.
+    mut params: ParamSet<(Query<Entity, RChanged>, RectrayPipeline)>,
+) {
+    dirty.clear();
+    dirty.extend(
+        scaled
+            .iter()
+            .filter(|(_, frame)| frame.scale_mode == ScaleMode::LayoutSpace)
+            .map(|(entity, _)| entity),
+    );
+    // Removing the last child removes `Children` instead of changing it.
+    // The former parent of an entity with a removed `Parent` loses it from `Children`,
+    // which is either changed or removed, the orphan itself could be a frame.
+    let removed = removed_children.read().chain(removed_parents.read());
+    for entity in params.p0().iter().chain(removed) {
+        // Frames could be nested under entities outside of a frame.
+        for ancestor in iter::once(entity).chain(parent_query.iter_ancestors(entity)) {
+            if root_query.contains(ancestor) {
+                dirty.insert(ancestor);
+            }
+        }
+    }
+    // Nested frames are computed with their outer frame.
+    let nested: Vec<_> = dirty
+        .iter()
+        .filter(|frame| {
+            parent_query
+                .iter_ancestors(**frame)
+                .any(|ancestor| dirty.contains(&ancestor))
+        })
+        .copied()
+        .collect();
+    for frame in nested {
+        dirty.remove(&frame);
+    }
+    let mut pipeline = params.p1();
+    pipeline.clean_up_removed(removed_cull.read(), removed_clip.read());
+    for (entity, root, children) in root_query.iter_many(dirty.iter()) {
+        pipeline.push_frame(entity, root, children);
+    }
+    pipeline.run();
+}
+
+/// Size [`RectrayFrame`]s with `fit_content` to the bounding box of their children.
+///
+/// Runs after [`compute_transform_2d`], the new dimension is laid out in the next frame.
+pub fn fit_frame_content(
+    mut frames: Query<(&mut RectrayFrame, &Transform, &Children)>,
+    children_query: Query<(&Transform, &RotatedRect)>,
+) {
+    for (mut frame, transform, children) in frames.iter_mut() {
+        if !frame.fit_content {
+            continue;
+        }
+        let scale = frame.layout_scale(transform).unwrap_or(Vec2::ONE);
+        let bounds = children_query
+            .iter_many(children)
+            .map(|(transform, rect)| {
+                // `Transform` is placed on the center of the rect.
+                RotatedRect {
+                    center: transform.translation.xy() * scale,
+                    ..*rect
+                }
+                .aabb()
+            })
+            .reduce(|a, b| a.union(b));
+        let Some(bounds) = bounds else {
+            continue;
+        };
+        let dimension = bounds.size() / scale;
+        if frame.dimension != dimension {
+            frame.dimension = dimension;
+        }
+    }
+}
+
+/// Size widgets with [`NinePatchFrame`] to their content.
+///
+/// Runs after [`compute_transform_2d`], the new dimension is laid out in the next frame.
+pub fn sync_nine_patch(
+    mut query: Query<(&NinePatchFrame, &mut Dimension)>,
+    content_query: Query<&RotatedRect>,
+) {
+    for (nine_patch, mut dimension) in query.iter_mut() {
+        let Ok(rect) = content_query.get(nine_patch.content) else {
+            continue;
+        };
+        let size = rect.dimension + nine_patch.border.size();
+        if dimension.0 != size {
+            dimension.0 = size;
+        }
+    }
+}
+
+/// Compute the layout of a single [`RectrayFrame`] on demand,
+/// can be run with `World::run_system_once_with`.
+///
+/// This only updates `Transform` and [`RotatedRect`],
+/// run it before [`TransformSystem::TransformPropagate`](bevy::transform::TransformSystem::TransformPropagate)
+/// or call `bevy::transform::systems::propagate_transforms` afterwards
+/// if `GlobalTransform` is needed in the same frame.
+pub fn compute_frame(
+    In(frame): In<Entity>,
+    root_query: Query<(&RectrayFrame, &Children)>,
+    mut pipeline: RectrayPipeline,
+) {
+    if let Ok((root, children)) = root_query.get(frame) {
+        pipeline.push_frame(frame, root, children);
+        pipeline.run();
+    }
+}
+
+/// Snap translations of entities with [`PixelSnap`].
+///
+/// Runs after [`compute_transform_2d`] so only the final output is snapped.
+pub fn snap_to_pixels(
+    mut query: Query<(&PixelSnap, &mut Transform), Or<(Changed<Transform>, Changed<PixelSnap>)>>,
+) {
+    for (snap, mut transform) in query.iter_mut() {
+        let snapped = snap.snap(transform.translation.xy());
+        if snapped != transform.translation.xy() {
+            transform.translation.x = snapped.x;
+            transform.translation.y = snapped.y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::event::Events;
+    use bevy::hierarchy::BuildChildren;
+    use bevy::math::Quat;
+    use bevy::tasks::TaskPoolBuilder;
+
+    use super::*;
+    use crate::layout::{AbsoluteLayout, StackLayout};
+
+    /// Lay out every frame, split into at most `max_tasks` tasks.
+    fn run_pipeline(world: &mut World, max_tasks: Option<usize>) {
+        let mut state =
+            SystemState::<(Query<(Entity, &RectrayFrame, &Children)>, RectrayPipeline)>::new(world);
+        let (frames, mut pipeline) = state.get_mut(world);
+        for (entity, frame, children) in frames.iter() {
+            pipeline.push_frame(entity, frame, children);
+        }
+        pipeline.run_in_tasks(max_tasks);
+        state.apply(world);
+    }
+
+    /// A link of a chain of containers, laid out between two items.
+    fn spawn_chain(world: &mut World, i: usize, d: usize, depth: usize) -> Entity {
+        // Alternate directions between links and chains.
+        let layout = match (i + d) % 2 {
+            0 => Container::new(StackLayout::HSTACK),
+            _ => Container::new(StackLayout::VSTACK),
+        };
+        let before = world
+            .spawn((
+                Transform2D::UNIT,
+                Dimension(Vec2::new(1.0 + (i % 7) as f32, 2.0 + d as f32)),
+            ))
+            .id();
+        let after = world
+            .spawn((
+                Transform2D::UNIT,
+                Dimension(Vec2::new(3.0 + d as f32, 1.0 + (i % 5) as f32)),
+            ))
+            .id();
+        let mut children = vec![before, after];
+        if d + 1 < depth {
+            children.insert(1, spawn_chain(world, i, d + 1, depth));
+        }
+        world
+            .spawn((
+                Transform2D {
+                    offset: Vec2::new(i as f32, d as f32),
+                    rotation: (i % 4) as f32 * 0.25,
+                    ..Transform2D::UNIT
+                },
+                Dimension(Vec2::ZERO),
+                layout.with_margin(Vec2::splat(d as f32)),
+            ))
+            .add_children(&children)
+            .id()
+    }
+
+    /// Lay out a frame with `width` chains `depth` deep, split into at most `max_tasks` tasks.
+    fn compute(
+        width: usize,
+        depth: usize,
+        max_tasks: Option<usize>,
+    ) -> Vec<(Entity, Transform, RotatedRect)> {
+        let mut world = World::new();
+        world.init_resource::<Events<RectrayLayoutChanged>>();
+        let chains: Vec<_> = (0..width)
+            .map(|i| spawn_chain(&mut world, i, 0, depth))
+            .collect();
+        world
+            .spawn(RectrayFrame::from_dimension(Vec2::new(1000.0, 800.0)))
+            .add_children(&chains);
+        // Run twice, the second pass must not change anything either way.
+        run_pipeline(&mut world, max_tasks);
+        run_pipeline(&mut world, max_tasks);
+        let mut rects: Vec<_> = world
+            .query::<(Entity, &Transform, &RotatedRect)>()
+            .iter(&world)
+            .map(|(entity, transform, rect)| (entity, *transform, *rect))
+            .collect();
+        rects.sort_by_key(|(entity, ..)| *entity);
+        rects
+    }
+
+    #[test]
+    fn parallel_matches_single_task() {
+        ComputeTaskPool::get_or_init(|| TaskPoolBuilder::new().num_threads(4).build());
+        let (width, depth) = (64, 8);
+        let expected = compute(width, depth, Some(1));
+        assert_eq!(expected.len(), width * depth * 3);
+        assert_eq!(compute(width, depth, None), expected);
+        assert_eq!(compute(width, depth, Some(7)), expected);
+    }
+
+    #[test]
+    fn measure_percent_of_parent() {
+        let mut world = World::new();
+        world.init_resource::<Events<RectrayLayoutChanged>>();
+        let percent = (
+            Transform2D::UNIT,
+            Dimension(Vec2::new(10.0, 10.0)),
+            DimensionPercent(Vec2::new(0.5, f32::NAN)),
+        );
+        let item = world.spawn(percent).id();
+        let padded = world
+            .spawn((
+                Transform2D::UNIT,
+                Dimension(Vec2::new(60.0, 40.0)),
+                Container::new(AbsoluteLayout::FIXED).with_padding(Vec2::new(5.0, 5.0)),
+            ))
+            .id();
+        world
+            .spawn(RectrayFrame::from_dimension(Vec2::new(200.0, 100.0)))
+            .add_children(&[item, padded]);
+        run_pipeline(&mut world, None);
+        assert_eq!(measure_subtree(&mut world, item), Vec2::new(100.0, 10.0));
+        let inner = world.spawn(percent).set_parent(padded).id();
+        // Padding is excluded from the dimension of the parent.
+        assert_eq!(measure_subtree(&mut world, inner), Vec2::new(30.0, 10.0));
+    }
+
+    #[test]
+    fn rotation_snap() {
+        use std::f32::consts::{FRAC_PI_4, PI, TAU};
+
+        let assert_near = |actual: f32, expected: f32| {
+            assert!((actual - expected).abs() < 1e-5, "{actual} != {expected}");
+        };
+        let snap = RotationSnap(PI / 12.0);
+        for (rotation, expected) in [
+            (0.0, 0.0),
+            (0.1, 0.0),
+            (0.14, PI / 12.0),
+            (FRAC_PI_4 + 0.05, FRAC_PI_4),
+            (-0.3, -PI / 12.0),
+            (PI, PI),
+            (TAU - 0.01, TAU),
+        ] {
+            assert_near(snap.snap(rotation), expected);
+        }
+        for invalid in [0.0, -PI / 12.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(RotationSnap(invalid).snap(0.3), 0.3);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Events<RectrayLayoutChanged>>();
+        let child = world
+            .spawn((
+                Transform2D {
+                    rotation: 0.3,
+                    ..Transform2D::UNIT
+                },
+                Dimension(Vec2::new(10.0, 10.0)),
+                snap,
+            ))
+            .id();
+        let parent = world
+            .spawn((
+                Transform2D {
+                    rotation: 0.1,
+                    ..Transform2D::UNIT
+                },
+                Dimension(Vec2::new(20.0, 20.0)),
+            ))
+            .add_child(child)
+            .id();
+        world
+            .spawn(RectrayFrame::from_dimension(Vec2::new(100.0, 100.0)))
+            .add_child(parent);
+        run_pipeline(&mut world, None);
+        // The rotation relative to the parent is snapped, the parent is not.
+        assert_near(world.get::<RotatedRect>(parent).unwrap().rotation, 0.1);
+        assert_near(world.get::<RotatedRect>(child).unwrap().rotation, PI / 12.0);
+        let transform = world.get::<Transform>(child).unwrap();
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(PI / 12.0), 1e-5));
     }
 }
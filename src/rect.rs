@@ -1,7 +1,8 @@
+use std::f32::consts::{PI, TAU};
 use std::ops::{Mul, Neg};
 
 use bevy::ecs::{component::Component, reflect::ReflectComponent};
-use bevy::math::{Quat, Rect, Vec2};
+use bevy::math::{IVec2, Quat, Rect, Vec2};
 use bevy::reflect::{std_traits::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy::transform::components::Transform;
 use serde::{Deserialize, Serialize};
@@ -27,6 +28,10 @@ impl Anchor {
     pub const TOP_CENTER: Self = Self(Vec2::new(0.0, 0.5));
     pub const TOP_RIGHT: Self = Self(Vec2::new(0.5, 0.5));
 
+    /// Distance from `0` beyond which an axis of an anchor is considered to be on a side,
+    /// used by [`Anchor::bucket`], [`Anchor::str_name`] and layouts that group items by anchor.
+    pub const BUCKET_THRESHOLD: f32 = 0.16;
+
     pub const fn new(v: Vec2) -> Self {
         Self(v)
     }
@@ -63,17 +68,65 @@ impl Anchor {
         }
     }
 
+    /// Linearly interpolate between two anchors, returns `INHERIT` if either is `INHERIT`.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        if self.is_inherit() || other.is_inherit() {
+            return Self::INHERIT;
+        }
+        Self(self.0.lerp(other.0, t))
+    }
+
+    /// Clamp the anchor inside the rectangle, i.e. `[-0.5, 0.5]`, `INHERIT` is unchanged.
+    pub fn clamped(self) -> Self {
+        if self.is_inherit() {
+            return self;
+        }
+        Self(self.0.clamp(Vec2::splat(-0.5), Vec2::splat(0.5)))
+    }
+
+    /// Returns the anchor on the opposite side, same as negation.
+    pub fn opposite(self) -> Self {
+        -self
+    }
+
+    /// Classify each axis as `-1`, `0` or `1` by whether it is
+    /// below `-threshold`, in between or above `threshold`, `NaN` is `0`.
+    ///
+    /// ```
+    /// # use bevy::math::IVec2;
+    /// # use bevy_rectray::Anchor;
+    /// let anchor = Anchor::custom(0.15, -0.17);
+    /// assert_eq!(anchor.bucket(), IVec2::new(0, -1));
+    /// assert_eq!(anchor.bucket_with_threshold(0.1), IVec2::new(1, -1));
+    /// ```
+    pub fn bucket_with_threshold(&self, threshold: f32) -> IVec2 {
+        let classify = |v: f32| match v {
+            v if v < -threshold => -1,
+            v if v > threshold => 1,
+            _ => 0,
+        };
+        IVec2::new(classify(self.0.x), classify(self.0.y))
+    }
+
+    /// Classify each axis with [`Anchor::BUCKET_THRESHOLD`],
+    /// see [`bucket_with_threshold`](Anchor::bucket_with_threshold).
+    pub fn bucket(&self) -> IVec2 {
+        self.bucket_with_threshold(Self::BUCKET_THRESHOLD)
+    }
+
     pub fn str_name(&self) -> &'static str {
-        match (self.0.x, self.0.y) {
-            x if x.0.is_nan() || x.1.is_nan() => "Inherit",
-            (x, y) if x < -0.16 && y < -0.16 => "BottomLeft",
-            (x, y) if x < -0.16 && y > 0.16 => "TopLeft",
-            (x, _) if x < -0.16 => "CenterLeft",
-            (x, y) if x > 0.16 && y < -0.16 => "BottomRight",
-            (x, y) if x > 0.16 && y > 0.16 => "TopRight",
-            (x, _) if x > 0.16 => "CenterRight",
-            (_, y) if y < -0.16 => "BottomCenter",
-            (_, y) if y > 0.16 => "TopCenter",
+        if self.0.is_nan() {
+            return "Inherit";
+        }
+        match self.bucket().to_array() {
+            [-1, -1] => "BottomLeft",
+            [-1, 1] => "TopLeft",
+            [-1, _] => "CenterLeft",
+            [1, -1] => "BottomRight",
+            [1, 1] => "TopRight",
+            [1, _] => "CenterRight",
+            [_, -1] => "BottomCenter",
+            [_, 1] => "TopCenter",
             _ => "Center",
         }
     }
@@ -115,6 +168,62 @@ impl From<Vec2> for Anchor {
     }
 }
 
+/// Standard anchors map to their named counterparts, other anchors map to `Custom`
+/// and `INHERIT` maps to `Center`.
+///
+/// ```
+/// # use bevy::math::Vec2;
+/// # use bevy_rectray::Anchor;
+/// use bevy::sprite::Anchor as BevyAnchor;
+/// let table = [
+///     (Anchor::BOTTOM_LEFT, BevyAnchor::BottomLeft),
+///     (Anchor::BOTTOM_CENTER, BevyAnchor::BottomCenter),
+///     (Anchor::BOTTOM_RIGHT, BevyAnchor::BottomRight),
+///     (Anchor::CENTER_LEFT, BevyAnchor::CenterLeft),
+///     (Anchor::CENTER, BevyAnchor::Center),
+///     (Anchor::CENTER_RIGHT, BevyAnchor::CenterRight),
+///     (Anchor::TOP_LEFT, BevyAnchor::TopLeft),
+///     (Anchor::TOP_CENTER, BevyAnchor::TopCenter),
+///     (Anchor::TOP_RIGHT, BevyAnchor::TopRight),
+///     (Anchor::custom(0.25, -0.1), BevyAnchor::Custom(Vec2::new(0.25, -0.1))),
+/// ];
+/// for (anchor, bevy_anchor) in table {
+///     assert_eq!(BevyAnchor::from(anchor), bevy_anchor);
+///     assert_eq!(Anchor::from(bevy_anchor), anchor);
+/// }
+/// assert_eq!(BevyAnchor::from(Anchor::INHERIT), BevyAnchor::Center);
+/// ```
+#[cfg(feature = "sprite")]
+impl From<Anchor> for bevy::sprite::Anchor {
+    fn from(val: Anchor) -> Self {
+        use bevy::sprite::Anchor as BevyAnchor;
+        if val.is_inherit() {
+            return BevyAnchor::Center;
+        }
+        [
+            (Anchor::BOTTOM_LEFT, BevyAnchor::BottomLeft),
+            (Anchor::BOTTOM_CENTER, BevyAnchor::BottomCenter),
+            (Anchor::BOTTOM_RIGHT, BevyAnchor::BottomRight),
+            (Anchor::CENTER_LEFT, BevyAnchor::CenterLeft),
+            (Anchor::CENTER, BevyAnchor::Center),
+            (Anchor::CENTER_RIGHT, BevyAnchor::CenterRight),
+            (Anchor::TOP_LEFT, BevyAnchor::TopLeft),
+            (Anchor::TOP_CENTER, BevyAnchor::TopCenter),
+            (Anchor::TOP_RIGHT, BevyAnchor::TopRight),
+        ]
+        .into_iter()
+        .find(|(anchor, _)| *anchor == val)
+        .map_or(BevyAnchor::Custom(val.0), |(_, anchor)| anchor)
+    }
+}
+
+#[cfg(feature = "sprite")]
+impl From<bevy::sprite::Anchor> for Anchor {
+    fn from(val: bevy::sprite::Anchor) -> Self {
+        Anchor(val.as_vec())
+    }
+}
+
 /// A rotated 2D rectangle.
 ///
 /// Note: `scale` is independent from dimension.
@@ -138,8 +247,14 @@ pub struct RotatedRect {
 #[derive(Debug, Copy, Clone)]
 pub struct ParentInfo {
     pub dimension: Vec2,
+    /// Center of the parent's rect in local space.
+    pub center: Vec2,
     pub at: Vec2,
     pub anchor: Option<Vec2>,
+    /// Overrides the child's `Dimension` if set.
+    pub child_dimension: Option<Vec2>,
+    /// Visible rect of a `RectrayClip` parent in local space.
+    pub clip: Option<RotatedRect>,
 }
 
 impl ParentInfo {
@@ -147,9 +262,15 @@ impl ParentInfo {
         self.anchor = Some(anc);
         self
     }
+
+    pub fn with_child_dimension(mut self, dim: Option<Vec2>) -> Self {
+        self.child_dimension = dim;
+        self
+    }
 }
 
 impl RotatedRect {
+    /// The rect ignoring rotation and scale.
     pub fn rect(&self) -> Rect {
         Rect {
             min: self.center - self.dimension / 2.,
@@ -163,6 +284,86 @@ impl RotatedRect {
         Vec2::from_angle(self.rotation).rotate(self.dimension * anchor) + self.center
     }
 
+    /// Linearly interpolate between two rects, `rotation` is interpolated along the shortest arc.
+    ///
+    /// ```
+    /// # use std::f32::consts::PI;
+    /// # use bevy::math::Vec2;
+    /// # use bevy_rectray::RotatedRect;
+    /// let a = RotatedRect { rotation: PI * 0.9, ..Default::default() };
+    /// let b = RotatedRect { center: Vec2::new(10., 0.), rotation: -PI * 0.9, ..Default::default() };
+    /// let mid = a.lerp(&b, 0.5);
+    /// assert_eq!(mid.center, Vec2::new(5., 0.));
+    /// assert!((mid.rotation.abs() - PI).abs() < 1e-4);
+    /// ```
+    pub fn lerp(&self, other: &RotatedRect, t: f32) -> RotatedRect {
+        let delta = (other.rotation - self.rotation + PI).rem_euclid(TAU) - PI;
+        RotatedRect {
+            center: self.center.lerp(other.center, t),
+            dimension: self.dimension.lerp(other.dimension, t),
+            rotation: self.rotation + delta * t,
+            z: self.z + (other.z - self.z) * t,
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+
+    /// Axis aligned bounding box of the rect, accounting for rotation and scale.
+    pub fn aabb(&self) -> Rect {
+        let [a, b, c, d] = self.corners();
+        Rect::from_corners(a.min(b).min(c).min(d), a.max(b).max(c).max(d))
+    }
+
+    /// Convert to a bevy [`Rect`], same as [`aabb`](Self::aabb),
+    /// use [`rect`](Self::rect) to ignore rotation and scale instead.
+    pub fn to_bevy_rect(&self) -> Rect {
+        self.aabb()
+    }
+
+    /// Find the screen space positions of the bottom left, bottom right, top right
+    /// and top left corners, scaled around `center`.
+    ///
+    /// Same as [`anchor`](Self::anchor) on these corners if `scale` is `1`.
+    #[inline]
+    pub fn corners(&self) -> [Vec2; 4] {
+        let rot = Vec2::from_angle(self.rotation);
+        let dim = self.dimension * self.scale;
+        [
+            Anchor::BOTTOM_LEFT,
+            Anchor::BOTTOM_RIGHT,
+            Anchor::TOP_RIGHT,
+            Anchor::TOP_LEFT,
+        ]
+        .map(|anchor| rot.rotate(dim * anchor) + self.center)
+    }
+
+    /// Find the edges between [`corners`](Self::corners), in counter-clockwise order
+    /// starting from the bottom edge.
+    #[inline]
+    pub fn edges(&self) -> [(Vec2, Vec2); 4] {
+        let [a, b, c, d] = self.corners();
+        [(a, b), (b, c), (c, d), (d, a)]
+    }
+
+    /// Returns true if two rects in the same space overlap or touch,
+    /// accounting for rotation and scale.
+    pub fn intersects(&self, other: &RotatedRect) -> bool {
+        let a = self.corners();
+        let b = other.corners();
+        let project = |corners: &[Vec2; 4], axis: Vec2| {
+            corners.iter().fold((f32::MAX, f32::MIN), |(min, max), p| {
+                let d = p.dot(axis);
+                (min.min(d), max.max(d))
+            })
+        };
+        [a[1] - a[0], a[3] - a[0], b[1] - b[0], b[3] - b[0]]
+            .into_iter()
+            .all(|axis| {
+                let (a_min, a_max) = project(&a, axis);
+                let (b_min, b_max) = project(&b, axis);
+                a_min <= b_max && b_min <= a_max
+            })
+    }
+
     // Half dimension
     #[inline]
     pub fn half_dim(&self) -> Vec2 {
@@ -175,6 +376,54 @@ impl RotatedRect {
         Vec2::from_angle(-self.rotation).rotate(position - self.center)
     }
 
+    /// Map a point in anchor units, `[-0.5, 0.5]` on the rect, to the space of this rect,
+    /// scaled around `center`.
+    ///
+    /// Same as [`anchor`](Self::anchor) if `scale` is `1`.
+    ///
+    /// ```
+    /// # use bevy::math::Vec2;
+    /// # use bevy_rectray::{Anchor, RotatedRect};
+    /// let rect = RotatedRect {
+    ///     center: Vec2::new(10., 5.),
+    ///     dimension: Vec2::new(4., 2.),
+    ///     rotation: 1.0,
+    ///     scale: Vec2::new(2., 3.),
+    ///     ..Default::default()
+    /// };
+    /// for (corner, anchor) in rect.corners().into_iter().zip([
+    ///     Anchor::BOTTOM_LEFT,
+    ///     Anchor::BOTTOM_RIGHT,
+    ///     Anchor::TOP_RIGHT,
+    ///     Anchor::TOP_LEFT,
+    /// ]) {
+    ///     assert!(rect.transform_point(anchor.as_vec()).abs_diff_eq(corner, 1e-4));
+    ///     assert!(rect.frame_to_local_unit(corner).abs_diff_eq(anchor.as_vec(), 1e-4));
+    /// }
+    /// ```
+    #[inline]
+    pub fn transform_point(&self, local_unit: Vec2) -> Vec2 {
+        Vec2::from_angle(self.rotation).rotate(self.dimension * self.scale * local_unit)
+            + self.center
+    }
+
+    /// Inverse of [`transform_point`](Self::transform_point),
+    /// not finite if the rect has a zero dimension or scale.
+    #[inline]
+    pub fn frame_to_local_unit(&self, point: Vec2) -> Vec2 {
+        self.local_space(point) / (self.dimension * self.scale)
+    }
+
+    /// Returns true if a point in the same space as this rect is inside this rect,
+    /// accounting for rotation and scale.
+    #[inline]
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.local_space(point)
+            .abs()
+            .cmple(self.half_dim() * self.scale)
+            .all()
+    }
+
     pub fn transform_at(&self, center: Vec2) -> Transform {
         Transform {
             translation: self.anchor((-center).into()).extend(self.z),
@@ -187,10 +436,10 @@ impl RotatedRect {
     #[inline]
     pub fn construct(parent: &ParentInfo, transform: &Transform2D, dimension: Vec2) -> Self {
         let parent_anchor = parent.anchor.unwrap_or(transform.get_parent_anchor());
-        let root = parent.dimension * parent_anchor;
+        let root = parent.center + parent.dimension * parent_anchor;
         // apply offset and dimension
         let self_center = root
-            + transform.offset
+            + transform.get_offset()
             + (transform.get_center() - transform.anchor.as_vec()) * dimension;
         Self {
             center: self_center,
@@ -0,0 +1,378 @@
+//! Scrolling support for [`Container`]s.
+//!
+//! Add [`RectrayScrollPlugin`] and [`RectrayScroll`] to a container with a [`LayoutRange`]
+//! to scroll it with the mouse wheel or touch.
+//!
+//! Requires `bevy_picking`'s [`RayMap`] to determine if a pointer is over the container.
+//!
+//! Add [`RectrayScrollbar`] to the thumb of a scrollbar, a child of its track,
+//! to display and drag the scroll position of a container.
+//!
+//! Add [`ScrollTarget`] to a container to animate its scroll position.
+
+use bevy::app::{App, Plugin, Update};
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventReader,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res},
+};
+use bevy::hierarchy::Parent;
+use bevy::input::{
+    mouse::{MouseScrollUnit, MouseWheel},
+    touch::Touches,
+};
+use bevy::math::Vec2;
+use bevy::picking::{
+    backend::ray::RayMap,
+    events::{Drag, DragStart, Pointer},
+    pointer::PointerId,
+};
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::time::Time;
+use bevy::transform::components::GlobalTransform;
+
+use crate::layout::{Container, LayoutRange};
+use crate::picking::ray_cast_rect;
+use crate::{Dimension, RotatedRect, Transform2D};
+
+/// Number of pixels considered one line of scrolling.
+pub const PIXELS_PER_LINE: f32 = 16.0;
+
+/// Axis of scrolling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum ScrollAxis {
+    /// Scroll with horizontal input.
+    X,
+    /// Scroll with vertical input.
+    #[default]
+    Y,
+}
+
+impl ScrollAxis {
+    /// Get the value on this axis.
+    pub fn get(&self, v: Vec2) -> f32 {
+        match self {
+            ScrollAxis::X => v.x,
+            ScrollAxis::Y => v.y,
+        }
+    }
+}
+
+/// Scroll the [`LayoutRange`] of a [`Container`] when a pointer is over it.
+///
+/// Scrolling up or dragging down shows earlier items.
+/// `Capped`, `Stepped` and `Tail` ranges scroll by whole steps, `Bounded` and `GridWindow` ranges scroll via
+/// [`Container::set_fac`], fractional scroll distance is accumulated between frames.
+#[derive(Debug, Clone, Copy, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct RectrayScroll {
+    /// Number of items, lines or pages scrolled per line of scrolling.
+    pub speed: f32,
+    /// Axis of scrolling.
+    pub axis: ScrollAxis,
+    /// Runtime computed scroll fraction of the container in `[0, 1]`,
+    /// see [`Container::get_fac`].
+    pub fac: f32,
+    #[reflect(ignore)]
+    accumulated: f32,
+}
+
+impl RectrayScroll {
+    pub const fn new(speed: f32, axis: ScrollAxis) -> Self {
+        RectrayScroll {
+            speed,
+            axis,
+            fac: 0.0,
+            accumulated: 0.0,
+        }
+    }
+}
+
+impl Default for RectrayScroll {
+    fn default() -> Self {
+        Self::new(1.0, ScrollAxis::Y)
+    }
+}
+
+/// Thumb of a scrollbar that displays and controls the scroll position of a [`Container`].
+///
+/// The thumb should be a child of the track and centered on the scrolling axis,
+/// its `offset` and [`Dimension`] on that axis are set by [`rectray_scrollbar`].
+/// The thumb shrinks as the `maximum` of the container grows.
+///
+/// Add [`RectrayPickable`](crate::RectrayPickable) to the thumb to make it draggable.
+#[derive(Debug, Clone, Copy, Component, Reflect)]
+#[reflect(Component)]
+#[require(Transform2D, Dimension)]
+pub struct RectrayScrollbar {
+    /// The scrolled [`Container`].
+    pub target: Entity,
+    /// Axis of the track.
+    pub axis: ScrollAxis,
+    #[reflect(ignore)]
+    grab: f32,
+}
+
+impl RectrayScrollbar {
+    pub const fn new(target: Entity, axis: ScrollAxis) -> Self {
+        RectrayScrollbar {
+            target,
+            axis,
+            grab: 0.0,
+        }
+    }
+
+    /// Convert a position on the track to `fac`, `free` is the length of track not covered by the thumb.
+    fn fac_at(&self, position: f32, free: f32) -> f32 {
+        match self.axis {
+            ScrollAxis::X => (position + free / 2.0) / free,
+            ScrollAxis::Y => (free / 2.0 - position) / free,
+        }
+    }
+
+    /// Convert `fac` to a position on the track, `free` is the length of track not covered by the thumb.
+    fn position_at(&self, fac: f32, free: f32) -> f32 {
+        match self.axis {
+            ScrollAxis::X => fac * free - free / 2.0,
+            ScrollAxis::Y => free / 2.0 - fac * free,
+        }
+    }
+}
+
+/// Move the scroll fraction of a [`Container`] toward `fac` at a constant rate,
+/// see [`Container::get_fac`].
+///
+/// `Stepped` ranges only change when the animation ends.
+/// Removed by [`rectray_scroll_target`] when `fac` is reached.
+#[derive(Debug, Clone, Copy, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct ScrollTarget {
+    /// Target scroll fraction in `[0, 1]`.
+    pub fac: f32,
+    /// Change of the scroll fraction per second.
+    pub rate: f32,
+    #[reflect(ignore)]
+    current: Option<f32>,
+}
+
+impl ScrollTarget {
+    pub const fn new(fac: f32, rate: f32) -> Self {
+        ScrollTarget {
+            fac,
+            rate,
+            current: None,
+        }
+    }
+}
+
+impl Default for ScrollTarget {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+/// [`Plugin`] for [`RectrayScroll`], [`RectrayScrollbar`] and [`ScrollTarget`].
+#[derive(Debug, Clone, Copy)]
+pub struct RectrayScrollPlugin;
+
+impl Plugin for RectrayScrollPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RectrayScroll>();
+        app.register_type::<RectrayScrollbar>();
+        app.register_type::<ScrollTarget>();
+        app.add_systems(
+            Update,
+            (rectray_scroll_target, rectray_scroll, rectray_scrollbar).chain(),
+        );
+    }
+}
+
+/// Animate containers with [`ScrollTarget`].
+pub fn rectray_scroll_target(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ScrollTarget, &mut Container)>,
+) {
+    for (entity, mut target, mut container) in query.iter_mut() {
+        // Animated separately since `get_fac` is rounded to whole items.
+        let current = target.current.unwrap_or_else(|| container.get_fac());
+        let goal = target.fac.clamp(0.0, 1.0);
+        let step = target.rate.abs() * time.delta_secs();
+        let next = if (goal - current).abs() <= step {
+            goal
+        } else {
+            current + step.copysign(goal - current)
+        };
+        target.current = Some(next);
+        if next == goal {
+            container.set_fac(goal);
+            commands.entity(entity).remove::<ScrollTarget>();
+        } else if !matches!(container.range, LayoutRange::Stepped { .. }) {
+            container.set_fac(next);
+        }
+    }
+}
+
+/// Scroll containers with [`RectrayScroll`].
+pub fn rectray_scroll(
+    map: Res<RayMap>,
+    touches: Res<Touches>,
+    mut wheel: EventReader<MouseWheel>,
+    mut query: Query<(
+        &mut RectrayScroll,
+        &mut Container,
+        &RotatedRect,
+        &GlobalTransform,
+    )>,
+) {
+    let wheel = wheel
+        .read()
+        .map(|ev| match ev.unit {
+            MouseScrollUnit::Line => Vec2::new(ev.x, ev.y),
+            MouseScrollUnit::Pixel => Vec2::new(ev.x, ev.y) / PIXELS_PER_LINE,
+        })
+        .sum::<Vec2>();
+    for (mut scroll, mut container, rect, global) in query.iter_mut() {
+        let is_over = |pointer: PointerId| {
+            map.iter().any(|(id, ray)| {
+                id.pointer == pointer
+                    && ray_cast_rect(*ray, global, rect).is_some_and(|x| x.is_inside())
+            })
+        };
+        let mut delta = 0.0;
+        if wheel != Vec2::ZERO && is_over(PointerId::Mouse) {
+            delta -= scroll.axis.get(wheel);
+        }
+        for touch in touches.iter() {
+            if is_over(PointerId::Touch(touch.id())) {
+                delta -= scroll.axis.get(touch.delta()) / PIXELS_PER_LINE;
+            }
+        }
+        if delta == 0.0 {
+            if scroll.fac != container.get_fac() {
+                scroll.fac = container.get_fac();
+            }
+            continue;
+        }
+        scroll.accumulated += delta * scroll.speed;
+        match container.range {
+            LayoutRange::All => scroll.accumulated = 0.0,
+            LayoutRange::Bounded { min, len }
+            | LayoutRange::GridWindow {
+                row: min,
+                rows: len,
+            } => {
+                if container.maximum > len {
+                    let target = min as f32 + scroll.accumulated;
+                    // Round towards `min`, so scrolling either way takes a full notch.
+                    let target = if scroll.accumulated < 0.0 {
+                        target.ceil()
+                    } else {
+                        target.floor()
+                    };
+                    let new_min = (target.max(0.0) as usize).min(container.maximum - len);
+                    if let LayoutRange::Bounded { min, .. }
+                    | LayoutRange::GridWindow { row: min, .. } = &mut container.range
+                    {
+                        *min = new_min;
+                    }
+                    scroll.accumulated -= new_min as f32 - min as f32;
+                    // Discard scrolling past either end.
+                    scroll.accumulated = scroll.accumulated.clamp(-1.0, 1.0);
+                } else {
+                    scroll.accumulated = 0.0;
+                }
+            }
+            LayoutRange::Capped { .. } | LayoutRange::Stepped { .. } | LayoutRange::Tail { .. } => {
+                while scroll.accumulated >= 1.0 {
+                    container.increment();
+                    scroll.accumulated -= 1.0;
+                }
+                while scroll.accumulated <= -1.0 {
+                    container.decrement();
+                    scroll.accumulated += 1.0;
+                }
+            }
+        }
+        scroll.fac = container.get_fac();
+    }
+}
+
+/// Size and position thumbs of [`RectrayScrollbar`]s and handle dragging.
+pub fn rectray_scrollbar(
+    map: Res<RayMap>,
+    mut drag_start: EventReader<Pointer<DragStart>>,
+    mut drag: EventReader<Pointer<Drag>>,
+    mut thumbs: Query<(
+        Entity,
+        &mut RectrayScrollbar,
+        &mut Transform2D,
+        &mut Dimension,
+        &Parent,
+    )>,
+    tracks: Query<(&RotatedRect, &GlobalTransform)>,
+    mut containers: Query<&mut Container>,
+) {
+    let drag_start: Vec<_> = drag_start
+        .read()
+        .map(|ev| (ev.target, ev.pointer_id))
+        .collect();
+    let drag: Vec<_> = drag.read().map(|ev| (ev.target, ev.pointer_id)).collect();
+    for (entity, mut scrollbar, mut transform, mut dimension, parent) in thumbs.iter_mut() {
+        let Ok((rect, global)) = tracks.get(parent.get()) else {
+            continue;
+        };
+        let Ok(mut container) = containers.get_mut(scrollbar.target) else {
+            continue;
+        };
+        let axis = scrollbar.axis;
+        // Position of a pointer on the track, in the track's local space.
+        let pointer_position = |pointer: PointerId| {
+            map.iter()
+                .filter(|(id, _)| id.pointer == pointer)
+                .find_map(|(_, ray)| ray_cast_rect(*ray, global, rect))
+                .map(|hit| axis.get(hit.local()))
+        };
+        let track_len = axis.get(rect.dimension);
+        let thumb_len = track_len * container.visible_fraction();
+        let free = track_len - thumb_len;
+        for (_, pointer) in drag_start.iter().filter(|(target, _)| *target == entity) {
+            if let Some(position) = pointer_position(*pointer) {
+                scrollbar.grab = position - axis.get(transform.offset);
+            }
+        }
+        if free > 0.0 {
+            let dragged = drag
+                .iter()
+                .filter(|(target, _)| *target == entity)
+                .filter_map(|(_, pointer)| pointer_position(*pointer))
+                .next_back();
+            if let Some(position) = dragged {
+                let fac = scrollbar.fac_at(position - scrollbar.grab, free);
+                container.set_fac(fac);
+            }
+        }
+        let position = scrollbar.position_at(container.get_fac(), free);
+        match axis {
+            ScrollAxis::X => {
+                if transform.offset.x != position {
+                    transform.offset.x = position;
+                }
+                if dimension.0.x != thumb_len {
+                    dimension.0.x = thumb_len;
+                }
+            }
+            ScrollAxis::Y => {
+                if transform.offset.y != position {
+                    transform.offset.y = position;
+                }
+                if dimension.0.y != thumb_len {
+                    dimension.0.y = thumb_len;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Integration with `bevy_sprite`, requires the `sprite` feature.
+//!
+//! Add [`RectraySpritePlugin`] and [`SyncSpriteSize`] to a `Sprite` entity
+//! to size the sprite to its laid out [`RotatedRect`].
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::{
+    component::Component,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::Query,
+};
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::sprite::Sprite;
+use bevy::transform::TransformSystem;
+
+use crate::{RectrayTransformSet, RotatedRect};
+
+/// Set `Sprite::custom_size` to [`RotatedRect::dimension`] after layout,
+/// so sprites stretched or shrunk by a container are rendered at their final size.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+#[require(Sprite)]
+pub struct SyncSpriteSize;
+
+/// [`Plugin`] for [`SyncSpriteSize`].
+///
+/// Runs after [`RectrayTransformSet`] and before transform propagation in [`PostUpdate`].
+#[derive(Debug, Clone, Copy)]
+pub struct RectraySpritePlugin;
+
+impl Plugin for RectraySpritePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SyncSpriteSize>();
+        app.add_systems(
+            PostUpdate,
+            sync_sprite_size
+                .after(RectrayTransformSet)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Copy [`RotatedRect::dimension`] to `Sprite::custom_size` for entities with [`SyncSpriteSize`].
+pub fn sync_sprite_size(
+    mut query: Query<(&RotatedRect, &mut Sprite), (With<SyncSpriteSize>, Changed<RotatedRect>)>,
+) {
+    for (rect, mut sprite) in query.iter_mut() {
+        if sprite.custom_size != Some(rect.dimension) {
+            sprite.custom_size = Some(rect.dimension);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Integration with `bevy_text`, requires the `text` feature.
+//!
+//! Add [`RectrayTextPlugin`] and [`SyncTextDimension`] to a `Text2d` entity
+//! to size its [`Dimension`] to the laid out text.
+//!
+//! Add [`SyncTextBounds`] to a `Text2d` child of a [`Container`] to wrap it
+//! to the width of the container.
+//!
+//! # Feedback
+//!
+//! Since the size of the text depends on the size of the container and the layout of the container
+//! depends on the size of the text, [`TextBounds`] is written from the previous frame's
+//! [`RotatedRect`], so a resized container wraps its text one frame late.
+//! Insert [`TextBoundsStabilization`] to lay out text and containers again in the same frame
+//! until the bounds no longer change.
+
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::{
+    component::Component,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Resource},
+    world::World,
+};
+use bevy::hierarchy::Parent;
+use bevy::reflect::{std_traits::ReflectDefault, Reflect};
+use bevy::text::{update_text2d_layout, TextBounds, TextLayoutInfo, Update2dText};
+use bevy::transform::TransformSystem;
+
+use crate::layout::Container;
+use crate::{compute_transform_2d, Dimension, RectrayTransformSet, RotatedRect};
+
+/// Set [`Dimension`] to the size of the laid out text in [`TextLayoutInfo`].
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+#[require(Dimension)]
+pub struct SyncTextDimension;
+
+/// Set the `width` of [`TextBounds`] to the width of the parent [`Container`]'s content,
+/// i.e. the width of its [`RotatedRect`] minus horizontal padding.
+///
+/// See the [module level documentation](self) for the one frame delay.
+#[derive(Debug, Clone, Copy, Component, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default)]
+#[require(TextBounds)]
+pub struct SyncTextBounds;
+
+/// If present, lay out [`SyncTextBounds`] text and containers again after [`RectrayTransformSet`]
+/// until [`TextBounds`] no longer change, up to `iterations` times.
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct TextBoundsStabilization {
+    /// Maximum number of passes per frame.
+    pub iterations: usize,
+}
+
+impl Default for TextBoundsStabilization {
+    fn default() -> Self {
+        Self { iterations: 2 }
+    }
+}
+
+/// [`Plugin`] for [`SyncTextDimension`] and [`SyncTextBounds`].
+///
+/// Runs after text layout and before [`RectrayTransformSet`] in [`PostUpdate`],
+/// so layouts see the size of the text in the same frame.
+#[derive(Debug, Clone, Copy)]
+pub struct RectrayTextPlugin;
+
+impl Plugin for RectrayTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SyncTextDimension>();
+        app.register_type::<SyncTextBounds>();
+        app.add_systems(PostUpdate, sync_text_bounds.before(Update2dText));
+        app.add_systems(
+            PostUpdate,
+            sync_text_dimension
+                .after(Update2dText)
+                .before(RectrayTransformSet),
+        );
+        app.add_systems(
+            PostUpdate,
+            stabilize_text_bounds
+                .after(RectrayTransformSet)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Copy the size in [`TextLayoutInfo`] to [`Dimension`] for entities with [`SyncTextDimension`].
+pub fn sync_text_dimension(
+    mut query: Query<
+        (&TextLayoutInfo, &mut Dimension),
+        (With<SyncTextDimension>, Changed<TextLayoutInfo>),
+    >,
+) {
+    for (info, mut dimension) in query.iter_mut() {
+        if dimension.0 != info.size {
+            dimension.0 = info.size;
+        }
+    }
+}
+
+/// Set [`TextBounds`] of entities with [`SyncTextBounds`] from their parent [`Container`].
+pub fn sync_text_bounds(
+    query: Query<(&mut TextBounds, &Parent), With<SyncTextBounds>>,
+    containers: Query<(&RotatedRect, &Container)>,
+) {
+    update_text_bounds(query, containers);
+}
+
+/// Returns `true` if any [`TextBounds`] changed.
+fn update_text_bounds(
+    mut query: Query<(&mut TextBounds, &Parent), With<SyncTextBounds>>,
+    containers: Query<(&RotatedRect, &Container)>,
+) -> bool {
+    let mut changed = false;
+    for (mut bounds, parent) in query.iter_mut() {
+        let Ok((rect, container)) = containers.get(parent.get()) else {
+            continue;
+        };
+        let width = (rect.dimension.x - container.padding.size().x).max(0.0);
+        if bounds.width != Some(width) {
+            bounds.width = Some(width);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Run text layout and [`compute_transform_2d`] again if [`TextBoundsStabilization`] is present
+/// and [`TextBounds`] changed after layout.
+pub fn stabilize_text_bounds(world: &mut World) {
+    let Some(stabilization) = world.get_resource::<TextBoundsStabilization>() else {
+        return;
+    };
+    for _ in 0..stabilization.iterations {
+        if !world.run_system_cached(update_text_bounds).unwrap_or(false) {
+            return;
+        }
+        let _ = world.run_system_cached(update_text2d_layout);
+        let _ = world.run_system_cached(sync_text_dimension);
+        let _ = world.run_system_cached(compute_transform_2d);
+    }
+}
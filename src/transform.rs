@@ -1,8 +1,8 @@
-use crate::layout::LayoutControl;
-use crate::rect::Anchor;
+use crate::layout::{Insets, LayoutControl};
+use crate::rect::{Anchor, ParentInfo};
 use crate::RotatedRect;
-use bevy::ecs::{component::Component, reflect::ReflectComponent};
-use bevy::math::Vec2;
+use bevy::ecs::{component::Component, entity::Entity, reflect::ReflectComponent};
+use bevy::math::{EulerRot, Vec2, Vec3, Vec3Swizzles};
 use bevy::prelude::{Transform, Visibility};
 use bevy::reflect::{std_traits::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
@@ -28,6 +28,9 @@ pub struct Transform2D {
     pub center: Anchor,
     /// Offset from parent's anchor.
     pub offset: Vec2,
+    /// Space `offset` is applied in, default is [`OffsetSpace::Parent`].
+    #[serde(default)]
+    pub offset_space: OffsetSpace,
     /// Z depth.
     /// By default this is `0.01`.
     pub z: f32,
@@ -48,16 +51,127 @@ impl Transform2D {
         self.parent_anchor.or(self.anchor).into()
     }
 
+    /// Offset in the parent's space, `offset` rotated by `rotation` if `offset_space` is `Local`.
+    ///
+    /// ```
+    /// # use std::f32::consts::FRAC_PI_2;
+    /// # use bevy::math::Vec2;
+    /// # use bevy_rectray::{OffsetSpace, Transform2D};
+    /// let transform = Transform2D::UNIT
+    ///     .with_offset(Vec2::new(10., 0.))
+    ///     .with_rotation(FRAC_PI_2);
+    /// assert_eq!(transform.get_offset(), Vec2::new(10., 0.));
+    /// let local = transform.with_offset_space(OffsetSpace::Local).get_offset();
+    /// assert!(local.abs_diff_eq(Vec2::new(0., 10.), 1e-5));
+    /// ```
+    #[inline]
+    pub fn get_offset(&self) -> Vec2 {
+        match self.offset_space {
+            OffsetSpace::Parent => self.offset,
+            OffsetSpace::Local => Vec2::from_angle(self.rotation).rotate(self.offset),
+        }
+    }
+
     pub const UNIT: Self = Self {
         anchor: Anchor::CENTER,
         parent_anchor: Anchor::INHERIT,
         center: Anchor::CENTER,
         offset: Vec2::ZERO,
+        offset_space: OffsetSpace::Parent,
         rotation: 0.0,
         z: 0.01,
         scale: Vec2::ONE,
     };
 
+    /// Place the item on `anchor` of its parent, with an offset.
+    ///
+    /// ```
+    /// # use bevy::math::Vec2;
+    /// # use bevy_rectray::{Anchor, Transform2D};
+    /// // Top left corner of the item is placed 10 pixels right of the top left corner of its parent.
+    /// let transform = Transform2D::at(Anchor::TOP_LEFT, Vec2::new(10., 0.));
+    /// assert_eq!(transform.get_parent_anchor(), Anchor::TOP_LEFT.as_vec());
+    /// ```
+    #[inline]
+    pub const fn at(anchor: Anchor, offset: Vec2) -> Self {
+        Self {
+            anchor,
+            parent_anchor: Anchor::INHERIT,
+            offset,
+            ..Self::UNIT
+        }
+    }
+
+    /// Create a centered [`Transform2D`] that outputs a `Transform`
+    /// with the same translation, rotation around the z axis and scale.
+    #[inline]
+    pub const fn from_translation_rotation_scale(
+        translation: Vec3,
+        rotation: f32,
+        scale: Vec2,
+    ) -> Self {
+        Self {
+            offset: Vec2::new(translation.x, translation.y),
+            z: translation.z,
+            rotation,
+            scale,
+            ..Self::UNIT
+        }
+    }
+
+    /// Create a centered [`Transform2D`] from a `Transform`,
+    /// rotation around the x and y axis and scale on the z axis are discarded.
+    #[inline]
+    pub fn from_transform(transform: &Transform) -> Self {
+        Self::from_translation_rotation_scale(
+            transform.translation,
+            transform.rotation.to_euler(EulerRot::ZYX).0,
+            transform.scale.xy(),
+        )
+    }
+
+    /// Compute the `Transform` the pipeline outputs for an item of `dimension`,
+    /// as if its parent has zero dimension, i.e. `parent_anchor` is placed on the parent's center.
+    ///
+    /// ```
+    /// # use bevy::math::{Vec2, Vec3};
+    /// # use bevy_rectray::{Anchor, Transform2D};
+    /// let transform = Transform2D::at(Anchor::BOTTOM_LEFT, Vec2::new(10., 0.))
+    ///     .to_transform(Vec2::new(4., 2.));
+    /// assert_eq!(transform.translation, Vec3::new(12., 1., 0.01));
+    /// ```
+    pub fn to_transform(&self, dimension: Vec2) -> Transform {
+        let parent = ParentInfo {
+            dimension: Vec2::ZERO,
+            center: Vec2::ZERO,
+            at: Vec2::ZERO,
+            anchor: None,
+            child_dimension: None,
+            clip: None,
+        };
+        RotatedRect::construct(&parent, self, dimension).transform_at(self.get_center())
+    }
+
+    /// Set `anchor`, `parent_anchor` and `center` to the same anchor.
+    ///
+    /// This places the item on `anchor` of its parent,
+    /// rotating and scaling around that anchor.
+    ///
+    /// ```
+    /// # use bevy_rectray::{Anchor, Transform2D};
+    /// let transform = Transform2D::anchored(Anchor::BOTTOM_RIGHT);
+    /// assert_eq!(transform.get_center(), Anchor::BOTTOM_RIGHT.as_vec());
+    /// ```
+    #[inline]
+    pub const fn anchored(anchor: Anchor) -> Self {
+        Self {
+            anchor,
+            parent_anchor: anchor,
+            center: anchor,
+            ..Self::UNIT
+        }
+    }
+
     /// Set offset.
     #[inline]
     pub fn with_offset(mut self, offset: Vec2) -> Self {
@@ -65,6 +179,13 @@ impl Transform2D {
         self
     }
 
+    /// Set the space `offset` is applied in.
+    #[inline]
+    pub fn with_offset_space(mut self, space: OffsetSpace) -> Self {
+        self.offset_space = space;
+        self
+    }
+
     /// Set rotation.
     #[inline]
     pub fn with_rotation(mut self, rot: f32) -> Self {
@@ -114,6 +235,16 @@ impl Default for Transform2D {
     }
 }
 
+/// Space [`Transform2D::offset`] is applied in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Reflect)]
+pub enum OffsetSpace {
+    /// Along the axes of the parent.
+    #[default]
+    Parent,
+    /// Along the axes of the widget, rotated by [`Transform2D::rotation`].
+    Local,
+}
+
 /// Dimension of the widget, this is a suggestion and can be modified via `Layout`.
 #[derive(Debug, Clone, Copy, PartialEq, Default, Component, Serialize, Deserialize, Reflect)]
 #[reflect(Component, Serialize, Deserialize)]
@@ -122,3 +253,234 @@ pub struct Dimension(pub Vec2);
 impl Dimension {
     pub const ZERO: Dimension = Dimension(Vec2::ZERO);
 }
+
+/// Minimum, maximum and preferred size of a widget,
+/// resolved into its effective [`Dimension`] before layout.
+///
+/// `max` of [`f32::INFINITY`] means unconstrained, if `min > max`, `min` takes priority.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct DimensionConstraints {
+    pub min: Vec2,
+    pub max: Vec2,
+    /// If set, used instead of [`Dimension`].
+    pub preferred: Option<Vec2>,
+}
+
+impl DimensionConstraints {
+    pub const UNCONSTRAINED: DimensionConstraints = DimensionConstraints {
+        min: Vec2::ZERO,
+        max: Vec2::INFINITY,
+        preferred: None,
+    };
+
+    /// Clamp `preferred` or `dimension` into `[min, max]`.
+    pub fn resolve(&self, dimension: Vec2) -> Vec2 {
+        self.preferred
+            .unwrap_or(dimension)
+            .min(self.max)
+            .max(self.min)
+    }
+}
+
+impl Default for DimensionConstraints {
+    fn default() -> Self {
+        Self::UNCONSTRAINED
+    }
+}
+
+/// How [`AspectRatio`] resolves a [`Dimension`] with a different aspect ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Reflect)]
+pub enum AspectMode {
+    /// Largest size that fits inside the dimension.
+    #[default]
+    Fit,
+    /// Smallest size that covers the dimension.
+    Fill,
+}
+
+/// Lock the width to height ratio of a widget, applied after [`DimensionConstraints`].
+#[derive(Debug, Clone, Copy, PartialEq, Component, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct AspectRatio {
+    /// Width divided by height, ignored if not positive.
+    pub ratio: f32,
+    pub mode: AspectMode,
+}
+
+impl AspectRatio {
+    pub const fn fit(ratio: f32) -> Self {
+        AspectRatio {
+            ratio,
+            mode: AspectMode::Fit,
+        }
+    }
+
+    pub const fn fill(ratio: f32) -> Self {
+        AspectRatio {
+            ratio,
+            mode: AspectMode::Fill,
+        }
+    }
+
+    /// Adjust `dimension` to match the aspect ratio.
+    pub fn resolve(&self, dimension: Vec2) -> Vec2 {
+        if !(self.ratio > 0.0 && self.ratio.is_finite()) {
+            return dimension;
+        }
+        let wider = dimension.x > dimension.y * self.ratio;
+        match (self.mode, wider) {
+            (AspectMode::Fit, true) | (AspectMode::Fill, false) => {
+                Vec2::new(dimension.y * self.ratio, dimension.y)
+            }
+            (AspectMode::Fit, false) | (AspectMode::Fill, true) => {
+                Vec2::new(dimension.x, dimension.x / self.ratio)
+            }
+        }
+    }
+}
+
+impl Default for AspectRatio {
+    fn default() -> Self {
+        Self::fit(1.0)
+    }
+}
+
+/// Size of a widget as a fraction of its parent's dimension, resolved before
+/// [`DimensionConstraints`] and [`AspectRatio`].
+///
+/// Axes that are `NaN` use [`Dimension`] instead, so one axis can be relative and the other absolute.
+/// For children of a [`Container`](crate::layout::Container), the parent's dimension
+/// excludes padding.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct DimensionPercent(pub Vec2);
+
+impl DimensionPercent {
+    /// Use [`Dimension`] on both axes.
+    pub const NONE: DimensionPercent = DimensionPercent(Vec2::NAN);
+
+    /// Relative width, use [`Dimension`] for height.
+    pub const fn width(fraction: f32) -> Self {
+        DimensionPercent(Vec2::new(fraction, f32::NAN))
+    }
+
+    /// Relative height, use [`Dimension`] for width.
+    pub const fn height(fraction: f32) -> Self {
+        DimensionPercent(Vec2::new(f32::NAN, fraction))
+    }
+
+    /// Replace axes of `dimension` that are not `NaN` with a fraction of `parent`.
+    pub fn resolve(&self, dimension: Vec2, parent: Vec2) -> Vec2 {
+        Vec2::select(self.0.is_nan_mask(), dimension, self.0 * parent)
+    }
+}
+
+impl Default for DimensionPercent {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Size a widget to the [`RotatedRect`] of `content` expanded by `border`,
+/// e.g. the background of a bordered panel that fits a content container.
+///
+/// `content` is usually a sibling with the same anchor, the [`Dimension`] of this widget
+/// is updated after layout and applied in the next frame.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct NinePatchFrame {
+    /// The entity whose rect is wrapped.
+    pub content: Entity,
+    /// Border around the content.
+    pub border: Insets,
+}
+
+impl NinePatchFrame {
+    pub fn new(content: Entity, border: impl Into<Insets>) -> Self {
+        NinePatchFrame {
+            content,
+            border: border.into(),
+        }
+    }
+}
+
+/// Round the `Transform` translation computed by `bevy_rectray` to the nearest `1 / scale`,
+/// to avoid shimmering of pixel art.
+///
+/// Only the translation relative to the parent is snapped,
+/// so parents should also be snapped for output to land on whole pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Component, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct PixelSnap {
+    /// Number of snapping points per unit, ignored if not positive.
+    pub scale: f32,
+}
+
+impl PixelSnap {
+    /// Snap `translation` to the nearest `1 / scale`.
+    pub fn snap(&self, translation: Vec2) -> Vec2 {
+        if !(self.scale > 0.0 && self.scale.is_finite()) {
+            return translation;
+        }
+        (translation * self.scale).round() / self.scale
+    }
+}
+
+impl Default for PixelSnap {
+    fn default() -> Self {
+        PixelSnap { scale: 1.0 }
+    }
+}
+
+/// Round the computed rotation of a widget to the nearest multiple of this angle in radians,
+/// i.e. `PI / 12` for 15 degree increments.
+///
+/// Only the rotation relative to the parent is snapped,
+/// the accumulated rotation is snapped if all ancestors are snapped to the same increment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Component, Serialize, Deserialize, Reflect)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct RotationSnap(pub f32);
+
+impl RotationSnap {
+    /// Snap `rotation` to the nearest multiple, ignored if not positive.
+    pub fn snap(&self, rotation: f32) -> f32 {
+        if !(self.0 > 0.0 && self.0.is_finite()) {
+            return rotation;
+        }
+        (rotation / self.0).round() * self.0
+    }
+}
+
+/// Bias the computed `z` of a widget by `index` times [`ZIndex::STEP`],
+/// changing render and pick order among siblings without changing [`Transform2D::z`].
+///
+/// Since `z` is relative to the parent, descendants move with the widget
+/// and keep their spacing, a `ZIndex` on a child only competes with its siblings.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+    Component,
+    Serialize,
+    Deserialize,
+    Reflect,
+)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct ZIndex(pub i32);
+
+impl ZIndex {
+    /// Distance in `z` between two adjacent indices,
+    /// larger than the default [`Transform2D::z`] spacing of a deep hierarchy.
+    pub const STEP: f32 = 1.0;
+
+    /// The `z` offset added to the computed `z`.
+    pub fn bias(&self) -> f32 {
+        self.0 as f32 * Self::STEP
+    }
+}
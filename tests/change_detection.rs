@@ -0,0 +1,217 @@
+//! Frames are only recomputed if their subtree changed,
+//! changes made after a frame settles must still be picked up.
+
+use bevy::app::{App, Update};
+use bevy::ecs::entity::Entity;
+use bevy::hierarchy::BuildChildren;
+use bevy::math::Vec2;
+use bevy::render::view::Visibility;
+use bevy_rectray::layout::{
+    ClipState, Container, LayoutRange, RectrayClip, RectrayCull, StackLayout,
+};
+use bevy_rectray::{
+    compute_transform_2d, Anchor, Dimension, RectrayFrame, RectrayLayoutChanged, RotatedRect,
+    Transform2D,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_event::<RectrayLayoutChanged>();
+    app.add_systems(Update, compute_transform_2d);
+    app
+}
+
+/// Run until the frame settles, the container is marked as changed in the first pass.
+fn settle(app: &mut App) {
+    for _ in 0..3 {
+        app.update();
+    }
+}
+
+fn rect(app: &App, entity: Entity) -> RotatedRect {
+    *app.world().get::<RotatedRect>(entity).unwrap()
+}
+
+fn widget(app: &mut App, dimension: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((Transform2D::UNIT, Dimension(dimension)))
+        .id()
+}
+
+/// A frame with a horizontal stack of two items.
+fn setup(app: &mut App) -> (Entity, Entity, Entity, Entity) {
+    let frame = app
+        .world_mut()
+        .spawn(RectrayFrame::from_dimension(Vec2::new(100.0, 100.0)))
+        .id();
+    let container = app
+        .world_mut()
+        .spawn((Container::new(StackLayout::HSTACK), Dimension(Vec2::ZERO)))
+        .id();
+    let a = widget(app, Vec2::new(10.0, 10.0));
+    let b = widget(app, Vec2::new(20.0, 10.0));
+    app.world_mut().entity_mut(frame).add_child(container);
+    app.world_mut().entity_mut(container).add_children(&[a, b]);
+    settle(app);
+    (frame, container, a, b)
+}
+
+#[test]
+fn transform_2d_change() {
+    let mut app = app();
+    let (frame, ..) = setup(&mut app);
+    let item = widget(&mut app, Vec2::new(10.0, 10.0));
+    app.world_mut().entity_mut(frame).add_child(item);
+    settle(&mut app);
+    assert_eq!(rect(&app, item).center, Vec2::ZERO);
+    app.world_mut().get_mut::<Transform2D>(item).unwrap().offset = Vec2::new(5.0, 0.0);
+    app.update();
+    assert_eq!(rect(&app, item).center, Vec2::new(5.0, 0.0));
+    app.world_mut().get_mut::<Transform2D>(item).unwrap().anchor = Anchor::BOTTOM_LEFT;
+    app.world_mut()
+        .get_mut::<Transform2D>(item)
+        .unwrap()
+        .parent_anchor = Anchor::BOTTOM_LEFT;
+    app.update();
+    // The item sits in the bottom left corner, still shifted by its offset.
+    assert_eq!(
+        rect(&app, item).center,
+        Vec2::new(-45.0, -45.0) + Vec2::new(5.0, 0.0)
+    );
+}
+
+#[test]
+fn dimension_change() {
+    let mut app = app();
+    let (_, container, a, _) = setup(&mut app);
+    let width = rect(&app, container).dimension.x;
+    app.world_mut().get_mut::<Dimension>(a).unwrap().0 = Vec2::new(30.0, 10.0);
+    app.update();
+    assert_eq!(rect(&app, a).dimension, Vec2::new(30.0, 10.0));
+    assert_eq!(rect(&app, container).dimension.x, width + 20.0);
+}
+
+#[test]
+fn container_change() {
+    let mut app = app();
+    let (_, container, a, b) = setup(&mut app);
+    let width = rect(&app, container).dimension.x;
+    let before = rect(&app, b).center - rect(&app, a).center;
+    app.world_mut()
+        .get_mut::<Container>(container)
+        .unwrap()
+        .margin = Vec2::new(4.0, 0.0);
+    app.update();
+    let after = rect(&app, b).center - rect(&app, a).center;
+    assert_eq!(after.x, before.x + 4.0);
+    assert_eq!(rect(&app, container).dimension.x, width + 4.0);
+}
+
+#[test]
+fn children_change() {
+    let mut app = app();
+    let (_, container, ..) = setup(&mut app);
+    let width = rect(&app, container).dimension.x;
+    let c = widget(&mut app, Vec2::new(5.0, 10.0));
+    app.world_mut().entity_mut(container).add_child(c);
+    app.update();
+    assert_eq!(rect(&app, container).dimension.x, width + 5.0);
+    assert_eq!(rect(&app, c).dimension, Vec2::new(5.0, 10.0));
+    app.world_mut().entity_mut(container).remove_children(&[c]);
+    app.update();
+    assert_eq!(rect(&app, container).dimension.x, width);
+}
+
+#[test]
+fn last_child_removed() {
+    let mut app = app();
+    let (_, container, a, b) = setup(&mut app);
+    let width = rect(&app, container).dimension.x;
+    app.world_mut()
+        .entity_mut(container)
+        .remove_children(&[a, b]);
+    // `Children` is removed instead of changed.
+    assert!(app
+        .world()
+        .get::<bevy::hierarchy::Children>(container)
+        .is_none());
+    app.update();
+    assert_eq!(rect(&app, container).dimension.x, width - 30.0);
+    assert_eq!(app.world().get::<Container>(container).unwrap().maximum, 0);
+}
+
+#[test]
+fn last_child_despawned() {
+    let mut app = app();
+    let (_, container, a, b) = setup(&mut app);
+    let width = rect(&app, container).dimension.x;
+    app.world_mut().entity_mut(a).despawn();
+    app.world_mut().entity_mut(container).remove_children(&[b]);
+    app.update();
+    assert_eq!(rect(&app, container).dimension.x, width - 30.0);
+    assert_eq!(app.world().get::<Container>(container).unwrap().maximum, 0);
+}
+
+#[test]
+fn parent_removed() {
+    let mut app = app();
+    let (_, container, ..) = setup(&mut app);
+    let mut inner = RectrayFrame::from_dimension(Vec2::new(40.0, 40.0));
+    inner.at = Vec2::ZERO;
+    let inner = app.world_mut().spawn(inner).id();
+    let item = app
+        .world_mut()
+        .spawn((
+            Transform2D::anchored(Anchor::TOP_RIGHT),
+            Dimension(Vec2::new(10.0, 10.0)),
+        ))
+        .id();
+    app.world_mut().entity_mut(container).add_child(inner);
+    app.world_mut().entity_mut(inner).add_child(item);
+    settle(&mut app);
+    // Nested frames are laid out in the rect of their parent.
+    let nested = rect(&app, item);
+    // Once orphaned, the frame is a root frame with its own dimension,
+    // the item is pivoted at its anchor, the top right corner.
+    app.world_mut().entity_mut(inner).remove_parent();
+    app.update();
+    assert_ne!(rect(&app, item), nested);
+    assert_eq!(rect(&app, item).center, Vec2::new(20.0, 20.0));
+}
+
+#[test]
+fn cull_removed() {
+    let mut app = app();
+    let (_, container, a, b) = setup(&mut app);
+    app.world_mut()
+        .entity_mut(container)
+        .insert(RectrayCull)
+        .get_mut::<Container>()
+        .unwrap()
+        .range = LayoutRange::Bounded { min: 0, len: 1 };
+    app.update();
+    let visibility = |app: &App, entity| *app.world().get::<Visibility>(entity).unwrap();
+    assert_eq!(visibility(&app, a), Visibility::Inherited);
+    assert_eq!(visibility(&app, b), Visibility::Hidden);
+    app.world_mut()
+        .entity_mut(container)
+        .remove::<RectrayCull>();
+    app.update();
+    assert_eq!(visibility(&app, b), Visibility::Inherited);
+}
+
+#[test]
+fn clip_removed() {
+    let mut app = app();
+    let (_, container, a, b) = setup(&mut app);
+    app.world_mut().entity_mut(container).insert(RectrayClip);
+    app.update();
+    assert!(app.world().get::<ClipState>(a).is_some());
+    assert!(app.world().get::<ClipState>(b).is_some());
+    app.world_mut()
+        .entity_mut(container)
+        .remove::<RectrayClip>();
+    app.update();
+    assert!(app.world().get::<ClipState>(a).is_none());
+    assert!(app.world().get::<ClipState>(b).is_none());
+}
@@ -0,0 +1,55 @@
+//! RON round-trip of built-in layouts through [`LayoutObject`].
+
+use bevy_rectray::layout::{
+    AbsoluteLayout, Align, Bidi, BoundsLayout, CompactLayout, FlexLayout, GridLayout, Justify,
+    LayoutObject, ParagraphLayout, RadialLayout, SpanLayout, StackLayout, TableLayout, WrapLayout,
+};
+
+fn round_trip(layout: LayoutObject) {
+    let serialized = ron::to_string(&layout).unwrap();
+    let deserialized: LayoutObject = ron::from_str(&serialized).unwrap();
+    assert_eq!(
+        format!("{deserialized:?}"),
+        format!("{layout:?}"),
+        "{serialized}"
+    );
+    // The registered name is stable across a second round trip.
+    assert_eq!(ron::to_string(&deserialized).unwrap(), serialized);
+}
+
+#[test]
+fn builtin_layouts_round_trip() {
+    let layouts = [
+        LayoutObject::new(AbsoluteLayout::FIT),
+        LayoutObject::new(AbsoluteLayout::FIXED),
+        LayoutObject::new(BoundsLayout::PADDING),
+        LayoutObject::new(StackLayout::HSTACK),
+        LayoutObject::new(StackLayout::VSTACK.with_separator(2.0)),
+        LayoutObject::new(SpanLayout::HBOX.with_cross_align(Align::Start)),
+        LayoutObject::new(SpanLayout::VBOX.with_justify(Justify::SpaceBetween)),
+        LayoutObject::new(SpanLayout::HBOX.with_stretch()),
+        LayoutObject::new(ParagraphLayout::PARAGRAPH),
+        LayoutObject::new(ParagraphLayout::PARAGRAPH.with_bidi(Bidi::Auto)),
+        LayoutObject::new(FlexLayout::HFLEX),
+        LayoutObject::new(FlexLayout::VFLEX),
+        LayoutObject::new(CompactLayout::HCOMPACT),
+        LayoutObject::new(CompactLayout::VCOMPACT),
+        LayoutObject::new(WrapLayout::WRAP),
+        LayoutObject::new(GridLayout::new(3)),
+        LayoutObject::new(TableLayout::new(4)),
+        LayoutObject::new(
+            RadialLayout::new(10.0)
+                .with_start_angle(0.5)
+                .with_sweep(1.5),
+        ),
+    ];
+    for layout in layouts {
+        round_trip(layout);
+    }
+}
+
+#[test]
+fn unregistered_layout_is_an_error() {
+    assert!(ron::from_str::<LayoutObject>(r#"{"NotALayout": ()}"#).is_err());
+    assert!(ron::from_str::<LayoutObject>("{}").is_err());
+}
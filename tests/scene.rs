@@ -0,0 +1,72 @@
+//! Reflection round-trip of [`Container`] as used by scenes.
+
+use bevy::ecs::entity::Entity;
+use bevy::math::Vec2;
+use bevy::reflect::serde::{ReflectDeserializer, ReflectSerializer};
+use bevy::reflect::{FromReflect, TypeRegistry};
+use bevy_rectray::layout::{Container, Insets, LayoutItem, LayoutObject, LayoutRange, StackLayout};
+use serde::de::DeserializeSeed;
+
+fn registry() -> TypeRegistry {
+    let mut registry = TypeRegistry::default();
+    registry.register::<Container>();
+    registry.register::<LayoutObject>();
+    registry.register::<LayoutRange>();
+    registry.register::<Insets>();
+    registry
+}
+
+fn round_trip(container: &Container, registry: &TypeRegistry) -> Container {
+    let serialized = ron::to_string(&ReflectSerializer::new(container, registry)).unwrap();
+    let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+    let value = ReflectDeserializer::new(registry)
+        .deserialize(&mut deserializer)
+        .unwrap();
+    Container::from_reflect(value.as_ref()).unwrap()
+}
+
+fn items() -> Vec<LayoutItem> {
+    (0..6)
+        .map(|i| {
+            LayoutItem::new(
+                Entity::from_raw(i),
+                Vec2::ZERO,
+                Vec2::new(10.0 + i as f32, 4.0),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn container_round_trip() {
+    let registry = registry();
+    let ranges = [
+        LayoutRange::All,
+        LayoutRange::Bounded { min: 1, len: 3 },
+        LayoutRange::Capped { min: 2, len: 3 },
+        LayoutRange::Stepped { step: 1, len: 2 },
+        LayoutRange::GridWindow { row: 1, rows: 2 },
+        LayoutRange::Tail { len: 2, offset: 1 },
+    ];
+    for range in ranges {
+        let mut container = Container {
+            layout: LayoutObject::new(StackLayout::HSTACK.with_separator(2.0)),
+            margin: Vec2::new(1.0, 2.0),
+            padding: Insets {
+                left: 1.0,
+                right: 2.0,
+                top: 3.0,
+                bottom: 4.0,
+            },
+            range,
+            ..Default::default()
+        };
+        let mut loaded = round_trip(&container, &registry);
+        assert_eq!(loaded.range, range);
+        assert_eq!(format!("{loaded:?}"), format!("{container:?}"));
+        // The loaded layout places items identically.
+        let expected = container.compute(Vec2::new(100.0, 100.0), items());
+        let output = loaded.compute(Vec2::new(100.0, 100.0), items());
+        assert_eq!(format!("{output:?}"), format!("{expected:?}"));
+    }
+}